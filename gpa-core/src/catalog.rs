@@ -0,0 +1,255 @@
+// gpa-core/src/catalog.rs
+use crate::types::{Course, Requisite};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A single problem found in the catalog, located by a JSON path such as `$[3].unit`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Problem {
+    pub severity: Severity,
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{}: {}: {}", severity, self.path, self.message)
+    }
+}
+
+fn error(path: String, message: String) -> Problem {
+    Problem {
+        severity: Severity::Error,
+        path,
+        message,
+    }
+}
+
+fn warning(path: String, message: String) -> Problem {
+    Problem {
+        severity: Severity::Warning,
+        path,
+        message,
+    }
+}
+
+/// Validates a parsed `courses.json` document. Every entry is checked on its own so one
+/// malformed course does not hide problems in the others.
+pub fn lint(document: &Value) -> Vec<Problem> {
+    let Some(entries) = document.as_array() else {
+        return vec![error(
+            "$".to_string(),
+            "catalog must be a JSON array of courses".to_string(),
+        )];
+    };
+
+    let mut problems = vec![];
+    let mut courses = vec![];
+    for (index, entry) in entries.iter().enumerate() {
+        match serde_json::from_value::<Course>(entry.clone()) {
+            Ok(course) => courses.push((index, course)),
+            Err(err) => problems.push(error(format!("$[{}]", index), err.to_string())),
+        }
+    }
+
+    problems.extend(lint_courses(&courses));
+    problems
+}
+
+/// Checks courses that deserialized successfully, given with their array index.
+pub fn lint_courses(courses: &[(usize, Course)]) -> Vec<Problem> {
+    let mut problems = vec![];
+    let mut ids: HashMap<usize, usize> = HashMap::new();
+    let mut codes: HashMap<String, usize> = HashMap::new();
+
+    for (index, course) in courses {
+        let path = format!("$[{}]", index);
+
+        if let Some(first) = ids.insert(course.id, *index) {
+            problems.push(error(
+                format!("{}.id", path),
+                format!("duplicate id {} (also used at $[{}])", course.id, first),
+            ));
+        }
+        if course.id != *index {
+            problems.push(error(
+                format!("{}.id", path),
+                format!(
+                    "id {} does not match its position {} in the array; courses are looked up by index",
+                    course.id, index
+                ),
+            ));
+        }
+        if course.name.trim().is_empty() {
+            problems.push(error(format!("{}.name", path), "name is empty".to_string()));
+        }
+        if course.unit <= 0 {
+            problems.push(error(
+                format!("{}.unit", path),
+                format!("unit must be positive, got {}", course.unit),
+            ));
+        }
+        if course.code.trim().is_empty() {
+            problems.push(warning(
+                format!("{}.code", path),
+                "code is empty".to_string(),
+            ));
+        } else if let Some(first) = codes.insert(course.code.to_ascii_uppercase(), *index) {
+            problems.push(error(
+                format!("{}.code", path),
+                format!(
+                    "duplicate code \"{}\" (also used at $[{}])",
+                    course.code, first
+                ),
+            ));
+        }
+
+        let mut terms = HashMap::new();
+        for (o, offering) in course.offerings.iter().enumerate() {
+            let offering_path = format!("{}.offerings[{}]", path, o);
+            if let Some(first) = terms.insert(offering.term, o) {
+                problems.push(error(
+                    format!("{}.term", offering_path),
+                    format!(
+                        "term {} is already listed at {}.offerings[{}]",
+                        offering.term, path, first
+                    ),
+                ));
+            }
+            if offering.sections.is_empty() {
+                problems.push(error(
+                    format!("{}.sections", offering_path),
+                    "offering has no sections".to_string(),
+                ));
+            }
+            let mut sections = HashMap::new();
+            for (s, section) in offering.sections.iter().enumerate() {
+                let section_path = format!("{}.sections[{}]", offering_path, s);
+                if let Some(first) = sections.insert(section.code.as_str(), s) {
+                    problems.push(error(
+                        format!("{}.code", section_path),
+                        format!(
+                            "duplicate section \"{}\" (also at {}.sections[{}])",
+                            section.code, offering_path, first
+                        ),
+                    ));
+                }
+                if section.instructor.trim().is_empty() {
+                    problems.push(warning(
+                        format!("{}.instructor", section_path),
+                        "instructor is empty".to_string(),
+                    ));
+                }
+            }
+        }
+    }
+
+    for (index, course) in courses {
+        for (field, requisite) in [
+            ("prerequisites", &course.prerequisites),
+            ("corequisites", &course.corequisites),
+        ] {
+            let Some(requisite) = requisite else { continue };
+            let mut referenced = vec![];
+            collect_codes(requisite, &mut referenced);
+            for code in referenced {
+                if code.eq_ignore_ascii_case(&course.code) {
+                    problems.push(error(
+                        format!("$[{}].{}", index, field),
+                        format!("course lists itself (\"{}\") as a requisite", code),
+                    ));
+                } else if !codes.contains_key(&code.to_ascii_uppercase()) {
+                    problems.push(warning(
+                        format!("$[{}].{}", index, field),
+                        format!("\"{}\" is not in the catalog", code),
+                    ));
+                }
+            }
+        }
+    }
+
+    problems
+}
+
+fn collect_codes<'a>(requisite: &'a Requisite, codes: &mut Vec<&'a str>) {
+    match requisite {
+        Requisite::Course(code) => codes.push(code),
+        Requisite::All { all: items } | Requisite::Any { any: items } => {
+            for item in items {
+                collect_codes(item, codes);
+            }
+        }
+    }
+}
+
+/// What identifies a course between builds: its code, or its name when it has none.
+fn identity(course: &Course) -> String {
+    let code = course.code.trim();
+    if code.is_empty() {
+        format!("name:{}", course.name.trim())
+    } else {
+        code.to_ascii_uppercase()
+    }
+}
+
+/// Keeps the id each course has in `existing` (the catalog being replaced, matched by code)
+/// and numbers new courses after the highest existing id, in code (then name) order, so
+/// bookmarked `/courses/:id` links keep pointing at the same course. Courses are ordered by
+/// id; ids match positions as long as no existing course was dropped (see
+/// [`dropped_courses`]). Returns the input position of each course in the new order.
+pub fn assign_ids(courses: &mut Vec<Course>, existing: &[Course]) -> Vec<usize> {
+    let mut known: HashMap<String, usize> = existing
+        .iter()
+        .map(|course| (identity(course), course.id))
+        .collect();
+    let mut ids: Vec<Option<usize>> = courses
+        .iter()
+        .map(|course| known.remove(&identity(course)))
+        .collect();
+
+    let mut new: Vec<usize> = (0..courses.len()).filter(|&i| ids[i].is_none()).collect();
+    new.sort_by_key(|&i| {
+        (
+            courses[i].code.to_ascii_uppercase(),
+            courses[i].name.clone(),
+        )
+    });
+    let first_new = existing
+        .iter()
+        .map(|course| course.id + 1)
+        .max()
+        .unwrap_or(0);
+    for (id, i) in (first_new..).zip(new) {
+        ids[i] = Some(id);
+    }
+
+    let mut order: Vec<usize> = (0..courses.len()).collect();
+    order.sort_by_key(|&i| ids[i]);
+    let mut sorted: Vec<Course> = order.iter().map(|&i| courses[i].clone()).collect();
+    for (course, &i) in sorted.iter_mut().zip(&order) {
+        course.id = ids[i].expect("every course has an id");
+    }
+    *courses = sorted;
+    order
+}
+
+/// Courses in `existing` that are missing from `courses`. Their ids would leave gaps, so
+/// every later course would no longer sit at the position matching its id.
+pub fn dropped_courses<'a>(courses: &[Course], existing: &'a [Course]) -> Vec<&'a Course> {
+    let kept: HashSet<String> = courses.iter().map(identity).collect();
+    existing
+        .iter()
+        .filter(|course| !kept.contains(&identity(course)))
+        .collect()
+}
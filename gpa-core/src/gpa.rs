@@ -0,0 +1,44 @@
+// gpa-core/src/gpa.rs
+use crate::grading::point_to_pa;
+
+/// Units and quality points (grade points times units) summed over a set of courses.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct GpaTotals {
+    pub units: i32,
+    pub quality_points: f32,
+}
+
+impl GpaTotals {
+    /// Totals over `(units, percentage)` pairs, graded on the SFU scale.
+    pub fn from_grades(grades: impl IntoIterator<Item = (i32, f32)>) -> Self {
+        grades
+            .into_iter()
+            .map(|(units, grade)| (units, point_to_pa(grade)))
+            .collect()
+    }
+
+    pub fn add(&mut self, units: i32, grade_points: f32) {
+        self.units += units;
+        self.quality_points += grade_points * units as f32;
+    }
+
+    /// Unit-weighted GPA; zero when there are no units.
+    pub fn gpa(&self) -> f32 {
+        if self.units > 0 {
+            self.quality_points / self.units as f32
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Collects `(units, grade points)` pairs.
+impl FromIterator<(i32, f32)> for GpaTotals {
+    fn from_iter<I: IntoIterator<Item = (i32, f32)>>(iter: I) -> Self {
+        let mut totals = Self::default();
+        for (units, grade_points) in iter {
+            totals.add(units, grade_points);
+        }
+        totals
+    }
+}
@@ -0,0 +1,65 @@
+// gpa-core/src/grading.rs
+
+/// One letter grade on the SFU scale.
+pub struct GradeBand {
+    pub letter: &'static str,
+    /// Lowest percentage that earns the letter.
+    pub min: f32,
+    /// Grade points the letter is worth.
+    pub points: f32,
+    /// Percentage recorded when only the letter is known (representative point).
+    pub typical: f32,
+}
+
+/// The SFU scale, best grade first.
+pub const SFU_SCALE: [GradeBand; 9] = [
+    band("A+", 95.0, 4.33, 97.0),
+    band("A", 90.0, 4.00, 93.0),
+    band("A-", 85.0, 3.67, 90.0),
+    band("B+", 80.0, 3.33, 87.0),
+    band("B", 75.0, 3.00, 83.0),  // lowest passing
+    band("B-", 70.0, 2.67, 80.0), // fail in grad school
+    band("C+", 65.0, 2.33, 77.0), // fail
+    band("C", 60.0, 2.00, 73.0),  // fail
+    band("F", 0.0, 0.00, 50.0),
+];
+
+const fn band(letter: &'static str, min: f32, points: f32, typical: f32) -> GradeBand {
+    GradeBand {
+        letter,
+        min,
+        points,
+        typical,
+    }
+}
+
+/// Letter grades offered in the grade pickers, best first
+pub const LETTER_GRADES: [&str; 9] = ["A+", "A", "A-", "B+", "B", "B-", "C+", "C", "F"];
+
+/// The band a percentage falls in; `None` outside 0–100.
+pub fn band_for(point: f32) -> Option<&'static GradeBand> {
+    if !(0.0..=100.0).contains(&point) {
+        return None;
+    }
+    SFU_SCALE.iter().find(|band| point >= band.min)
+}
+
+/// Converts a letter grade to an average percentage score (representative point)
+pub fn letter_to_point(letter: &str) -> f32 {
+    SFU_SCALE
+        .iter()
+        .find(|band| band.letter == letter)
+        .map_or(0.0, |band| band.typical) // invalid input
+}
+
+/// Converts a percentage score to a letter grade
+pub fn point_to_letter(point: f32) -> String {
+    band_for(point)
+        .map(|band| band.letter.to_string())
+        .unwrap_or_default()
+}
+
+/// Converts a percentage score to GPA (PA) following the SFU scale
+pub fn point_to_pa(point: f32) -> f32 {
+    band_for(point).map_or(-1.0, |band| band.points) // invalid input
+}
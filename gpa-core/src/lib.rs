@@ -0,0 +1,8 @@
+// gpa-core/src/lib.rs
+//! Course model, grading scale, GPA aggregation and plan checks, independent of the UI.
+pub mod catalog;
+pub mod gpa;
+pub mod grading;
+pub mod prereq;
+pub mod types;
+pub mod validation;
@@ -0,0 +1,220 @@
+// gpa-core/src/prereq.rs
+use crate::types::{Course, Requisite, Term};
+use std::fmt;
+use std::str::FromStr;
+
+impl Requisite {
+    /// Whether the requirement holds given a predicate telling if a course code was taken.
+    pub fn is_satisfied(&self, taken: &dyn Fn(&str) -> bool) -> bool {
+        match self {
+            Requisite::Course(code) => taken(code),
+            Requisite::All { all } => all.iter().all(|r| r.is_satisfied(taken)),
+            Requisite::Any { any } => any.iter().any(|r| r.is_satisfied(taken)),
+        }
+    }
+
+    /// The part of the requirement that is still missing, if any. Satisfied branches of an
+    /// AND group are dropped; an unsatisfied OR group is reported whole.
+    pub fn unmet(&self, taken: &dyn Fn(&str) -> bool) -> Option<Requisite> {
+        match self {
+            Requisite::Course(code) => (!taken(code)).then(|| self.clone()),
+            Requisite::All { all } => {
+                let mut missing: Vec<Requisite> =
+                    all.iter().filter_map(|r| r.unmet(taken)).collect();
+                match missing.len() {
+                    0 => None,
+                    1 => missing.pop(),
+                    _ => Some(Requisite::All { all: missing }),
+                }
+            }
+            Requisite::Any { .. } => (!self.is_satisfied(taken)).then(|| self.clone()),
+        }
+    }
+}
+
+impl fmt::Display for Requisite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (items, joiner) = match self {
+            Requisite::Course(code) => return write!(f, "{}", code),
+            Requisite::All { all } => (all, " and "),
+            Requisite::Any { any } => (any, " or "),
+        };
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                write!(f, "{}", joiner)?;
+            }
+            match item {
+                Requisite::Course(_) => write!(f, "{}", item)?,
+                _ => write!(f, "({})", item)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Requisite {
+    type Err = String;
+
+    /// Parses the notation produced by `Display`, e.g. `CMPT 732 and (CMPT 726 or MATH 240)`.
+    /// `and` binds tighter than `or`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let spaced = s.replace('(', " ( ").replace(')', " ) ");
+        let tokens: Vec<&str> = spaced.split_whitespace().collect();
+        let mut pos = 0;
+        let requisite = parse_or(&tokens, &mut pos)?;
+        match tokens.get(pos) {
+            None => Ok(requisite),
+            Some(token) => Err(format!("unexpected \"{}\" in \"{}\"", token, s)),
+        }
+    }
+}
+
+fn is_keyword(token: &str, keyword: &str) -> bool {
+    token.eq_ignore_ascii_case(keyword)
+}
+
+fn parse_or(tokens: &[&str], pos: &mut usize) -> Result<Requisite, String> {
+    let mut items = vec![parse_and(tokens, pos)?];
+    while tokens.get(*pos).is_some_and(|t| is_keyword(t, "or")) {
+        *pos += 1;
+        items.push(parse_and(tokens, pos)?);
+    }
+    Ok(group(items, |any| Requisite::Any { any }))
+}
+
+fn parse_and(tokens: &[&str], pos: &mut usize) -> Result<Requisite, String> {
+    let mut items = vec![parse_atom(tokens, pos)?];
+    while tokens.get(*pos).is_some_and(|t| is_keyword(t, "and")) {
+        *pos += 1;
+        items.push(parse_atom(tokens, pos)?);
+    }
+    Ok(group(items, |all| Requisite::All { all }))
+}
+
+fn parse_atom(tokens: &[&str], pos: &mut usize) -> Result<Requisite, String> {
+    if tokens.get(*pos) == Some(&"(") {
+        *pos += 1;
+        let inner = parse_or(tokens, pos)?;
+        if tokens.get(*pos) != Some(&")") {
+            return Err("missing closing parenthesis".to_string());
+        }
+        *pos += 1;
+        return Ok(inner);
+    }
+
+    let start = *pos;
+    while let Some(token) = tokens.get(*pos) {
+        if matches!(*token, "(" | ")") || is_keyword(token, "and") || is_keyword(token, "or") {
+            break;
+        }
+        *pos += 1;
+    }
+    if start == *pos {
+        return Err(match tokens.get(*pos) {
+            Some(token) => format!("expected a course code before \"{}\"", token),
+            None => "expected a course code".to_string(),
+        });
+    }
+    Ok(Requisite::Course(tokens[start..*pos].join(" ")))
+}
+
+fn group(mut items: Vec<Requisite>, make: fn(Vec<Requisite>) -> Requisite) -> Requisite {
+    if items.len() == 1 {
+        items.pop().unwrap()
+    } else {
+        make(items)
+    }
+}
+
+/// A course in the student's plan together with the term it is taken in.
+pub struct PlannedCourse<'a> {
+    pub course: &'a Course,
+    pub term: Term,
+}
+
+pub enum RequisiteKind {
+    Prerequisite,
+    Corequisite,
+}
+
+pub struct PlanWarning {
+    pub course: String,
+    pub term: Term,
+    pub kind: RequisiteKind,
+    pub missing: Requisite,
+}
+
+impl fmt::Display for PlanWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            RequisiteKind::Prerequisite => write!(
+                f,
+                "{} is planned for {} before its prerequisite {}",
+                self.course, self.term, self.missing
+            ),
+            RequisiteKind::Corequisite => write!(
+                f,
+                "{} is planned for {} without its corequisite {} in the same or an earlier term",
+                self.course, self.term, self.missing
+            ),
+        }
+    }
+}
+
+/// Label used for a course in messages: its code when it has one, else its name.
+pub fn course_label(course: &Course) -> &str {
+    if course.code.is_empty() {
+        &course.name
+    } else {
+        &course.code
+    }
+}
+
+fn matches_code(course: &Course, code: &str) -> bool {
+    course.code.eq_ignore_ascii_case(code) || course.name.eq_ignore_ascii_case(code)
+}
+
+/// Checks every planned course against its requisites. Prerequisites must be planned in a
+/// strictly earlier term, corequisites in the same term or earlier.
+pub fn validate_plan(plan: &[PlannedCourse]) -> Vec<PlanWarning> {
+    let mut warnings = vec![];
+    for planned in plan {
+        let taken_before = |code: &str| {
+            plan.iter()
+                .any(|other| other.term < planned.term && matches_code(other.course, code))
+        };
+        let taken_by = |code: &str| {
+            plan.iter()
+                .any(|other| other.term <= planned.term && matches_code(other.course, code))
+        };
+
+        if let Some(missing) = planned
+            .course
+            .prerequisites
+            .as_ref()
+            .and_then(|r| r.unmet(&taken_before))
+        {
+            warnings.push(PlanWarning {
+                course: course_label(planned.course).to_string(),
+                term: planned.term,
+                kind: RequisiteKind::Prerequisite,
+                missing,
+            });
+        }
+        if let Some(missing) = planned
+            .course
+            .corequisites
+            .as_ref()
+            .and_then(|r| r.unmet(&taken_by))
+        {
+            warnings.push(PlanWarning {
+                course: course_label(planned.course).to_string(),
+                term: planned.term,
+                kind: RequisiteKind::Corequisite,
+                missing,
+            });
+        }
+    }
+    warnings.sort_by_key(|w| w.term);
+    warnings
+}
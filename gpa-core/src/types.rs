@@ -0,0 +1,145 @@
+// gpa-core/src/types.rs
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Deserialize, Serialize, Clone, PartialEq, Debug, Default)]
+pub struct Course {
+    pub id: usize,
+    #[serde(default)]
+    pub code: String,
+    pub name: String,
+    pub teacher: String,
+    pub description: String,
+    pub image: String,
+    pub unit: i32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub offerings: Vec<Offering>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prerequisites: Option<Requisite>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub corequisites: Option<Requisite>,
+}
+
+/// A requisite expression over course codes. In the catalog this is written as
+/// `"CMPT 732"`, `{"all": [...]}` or `{"any": [...]}`, nested as needed.
+#[derive(Deserialize, Serialize, Clone, PartialEq, Debug)]
+#[serde(untagged)]
+pub enum Requisite {
+    Course(String),
+    All { all: Vec<Requisite> },
+    Any { any: Vec<Requisite> },
+}
+
+/// SFU runs three terms a year, in this order.
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub enum Season {
+    Spring,
+    Summer,
+    Fall,
+}
+
+/// A term such as "2024 Fall". Ordered chronologically.
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct Term {
+    pub year: u16,
+    pub season: Season,
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:?}", self.year, self.season)
+    }
+}
+
+impl FromStr for Term {
+    type Err = String;
+
+    /// Accepts exactly "2024 Fall" or "Fall 2024".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let season = |part: &str| match part.to_ascii_lowercase().as_str() {
+            "spring" => Some(Season::Spring),
+            "summer" => Some(Season::Summer),
+            "fall" => Some(Season::Fall),
+            _ => None,
+        };
+        let year = |part: &str| part.parse::<u16>().ok();
+        let parts: Vec<&str> = s.split_whitespace().collect();
+        let term = match parts[..] {
+            [first, second] => match (season(first), year(second)) {
+                (Some(season), Some(year)) => Some(Term { year, season }),
+                _ => year(first)
+                    .zip(season(second))
+                    .map(|(year, season)| Term { year, season }),
+            },
+            _ => None,
+        };
+        term.ok_or_else(|| format!("invalid term \"{}\"", s))
+    }
+}
+
+impl TryFrom<String> for Term {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Term> for String {
+    fn from(term: Term) -> Self {
+        term.to_string()
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq, Debug)]
+pub struct MeetingTime {
+    pub days: String,
+    pub start: String,
+    pub end: String,
+    #[serde(default)]
+    pub room: String,
+}
+
+/// One section of a course in a given term, e.g. "D100".
+#[derive(Deserialize, Serialize, Clone, PartialEq, Debug)]
+pub struct Section {
+    pub code: String,
+    pub instructor: String,
+    #[serde(default)]
+    pub meeting_times: Vec<MeetingTime>,
+    pub campus: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, PartialEq, Debug)]
+pub struct Offering {
+    pub term: Term,
+    pub sections: Vec<Section>,
+}
+
+/// The term and section a student took a course in.
+#[derive(Deserialize, Serialize, Clone, PartialEq, Debug)]
+pub struct Enrollment {
+    pub term: Term,
+    pub section: String,
+}
+
+impl Course {
+    pub fn offering(&self, term: Term) -> Option<&Offering> {
+        self.offerings.iter().find(|offering| offering.term == term)
+    }
+
+    /// Instructor of the section the student took, falling back to `teacher`.
+    pub fn instructor_for(&self, enrollment: Option<&Enrollment>) -> &str {
+        enrollment
+            .and_then(|enrollment| {
+                self.offering(enrollment.term)?
+                    .sections
+                    .iter()
+                    .find(|section| section.code == enrollment.section)
+            })
+            .map(|section| section.instructor.as_str())
+            .unwrap_or(&self.teacher)
+    }
+}
@@ -0,0 +1,91 @@
+// gpa-core/src/validation.rs
+use std::fmt;
+
+/// A course field that can be entered by hand.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Field {
+    Grade,
+    Unit,
+    Name,
+}
+
+/// Why a value was rejected, worded for the user.
+#[derive(Clone, PartialEq, Debug)]
+pub struct FieldError {
+    pub field: Field,
+    pub message: String,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+fn invalid(field: Field, message: &str) -> FieldError {
+    FieldError {
+        field,
+        message: message.to_string(),
+    }
+}
+
+/// A percentage between 0 and 100.
+pub fn check_grade(grade: f32) -> Result<f32, FieldError> {
+    if (0.0..=100.0).contains(&grade) {
+        Ok(grade)
+    } else {
+        Err(invalid(Field::Grade, "Grade must be between 0 and 100"))
+    }
+}
+
+/// Most units one course can carry; a full-time co-op or thesis term is 15.
+pub const MAX_UNITS: i32 = 30;
+
+const UNIT_MESSAGE: &str = "Units must be a whole number from 1 to 30";
+
+/// A whole number of units from 1 to `MAX_UNITS`.
+pub fn check_unit(unit: i32) -> Result<i32, FieldError> {
+    if (1..=MAX_UNITS).contains(&unit) {
+        Ok(unit)
+    } else {
+        Err(invalid(Field::Unit, UNIT_MESSAGE))
+    }
+}
+
+pub fn check_name(name: &str) -> Result<(), FieldError> {
+    if name.trim().is_empty() {
+        Err(invalid(Field::Name, "Course name is required"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Parses a typed grade; surrounding whitespace is ignored.
+pub fn parse_grade(input: &str) -> Result<f32, FieldError> {
+    let grade = input
+        .trim()
+        .parse::<f32>()
+        .map_err(|_| invalid(Field::Grade, "Grade must be a number"))?;
+    check_grade(grade)
+}
+
+/// Parses typed units; surrounding whitespace is ignored.
+pub fn parse_unit(input: &str) -> Result<i32, FieldError> {
+    let unit = input
+        .trim()
+        .parse::<i32>()
+        .map_err(|_| invalid(Field::Unit, UNIT_MESSAGE))?;
+    check_unit(unit)
+}
+
+/// Every problem with a course as entered, in field order.
+pub fn course_problems(name: &str, unit: i32, grade: f32) -> Vec<FieldError> {
+    [
+        check_grade(grade).err(),
+        check_unit(unit).err(),
+        check_name(name).err(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
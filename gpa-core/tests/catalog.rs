@@ -0,0 +1,163 @@
+use gpa_core::catalog::{assign_ids, dropped_courses, lint, Severity};
+use gpa_core::types::Course;
+use serde_json::json;
+
+fn course(id: usize, code: &str) -> serde_json::Value {
+    json!({
+        "id": id,
+        "code": code,
+        "name": format!("{} course", code),
+        "teacher": "Teacher",
+        "description": "",
+        "image": "",
+        "unit": 3,
+    })
+}
+
+fn paths(document: &serde_json::Value, severity: Severity) -> Vec<String> {
+    lint(document)
+        .into_iter()
+        .filter(|problem| problem.severity == severity)
+        .map(|problem| problem.path)
+        .collect()
+}
+
+#[test]
+fn valid_catalog_has_no_problems() {
+    let document = json!([course(0, "CMPT 120"), course(1, "CMPT 225")]);
+    assert!(lint(&document).is_empty());
+}
+
+#[test]
+fn catalog_must_be_an_array() {
+    let problems = lint(&json!({"courses": []}));
+    assert_eq!(problems.len(), 1);
+    assert_eq!(problems[0].path, "$");
+    assert_eq!(problems[0].severity, Severity::Error);
+}
+
+#[test]
+fn reports_malformed_entries_without_hiding_others() {
+    let mut bad_unit = course(1, "CMPT 225");
+    bad_unit["unit"] = json!(0);
+    let document = json!([{"id": 0}, bad_unit]);
+    assert_eq!(paths(&document, Severity::Error), ["$[0]", "$[1].unit"]);
+}
+
+#[test]
+fn reports_duplicate_and_misplaced_ids_and_codes() {
+    let document = json!([course(0, "CMPT 120"), course(0, "cmpt 120")]);
+    let errors = paths(&document, Severity::Error);
+    assert_eq!(errors, ["$[1].id", "$[1].id", "$[1].code"]);
+}
+
+#[test]
+fn warns_about_unknown_requisites_and_rejects_self_references() {
+    let mut first = course(0, "CMPT 120");
+    first["prerequisites"] = json!("CMPT 999");
+    let mut second = course(1, "CMPT 225");
+    second["corequisites"] = json!({"any": ["CMPT 225", "CMPT 120"]});
+    let document = json!([first, second]);
+
+    assert_eq!(paths(&document, Severity::Warning), ["$[0].prerequisites"]);
+    assert_eq!(paths(&document, Severity::Error), ["$[1].corequisites"]);
+}
+
+#[test]
+fn checks_offerings_and_sections() {
+    let mut entry = course(0, "CMPT 120");
+    entry["offerings"] = json!([
+        {"term": "2024 Fall", "sections": []},
+        {"term": "Fall 2024", "sections": [
+            {"code": "D100", "instructor": "", "campus": "Burnaby"},
+            {"code": "D100", "instructor": "A", "campus": "Burnaby"},
+        ]},
+    ]);
+    let document = json!([entry]);
+
+    assert_eq!(
+        paths(&document, Severity::Error),
+        [
+            "$[0].offerings[0].sections",
+            "$[0].offerings[1].term",
+            "$[0].offerings[1].sections[1].code",
+        ]
+    );
+    assert_eq!(
+        paths(&document, Severity::Warning),
+        ["$[0].offerings[1].sections[0].instructor"]
+    );
+}
+
+#[test]
+fn problems_display_with_severity_and_path() {
+    let problems = lint(
+        &json!([{"id": 0, "code": "", "name": "X", "teacher": "", "description": "", "image": "", "unit": 3}]),
+    );
+    assert_eq!(problems.len(), 1);
+    assert_eq!(problems[0].to_string(), "warning: $[0].code: code is empty");
+}
+
+fn named(id: usize, code: &str) -> Course {
+    Course {
+        id,
+        code: code.to_string(),
+        name: code.to_string(),
+        ..Course::default()
+    }
+}
+
+fn codes_and_ids(courses: &[Course]) -> Vec<(&str, usize)> {
+    courses.iter().map(|c| (c.code.as_str(), c.id)).collect()
+}
+
+#[test]
+fn assign_ids_without_a_catalog_sorts_by_code() {
+    let mut courses = vec![
+        named(42, "MATH 240"),
+        named(42, "cmpt 726"),
+        named(42, "CMPT 120"),
+    ];
+    let order = assign_ids(&mut courses, &[]);
+
+    assert_eq!(order, [2, 1, 0]);
+    assert_eq!(
+        codes_and_ids(&courses),
+        [("CMPT 120", 0), ("cmpt 726", 1), ("MATH 240", 2)]
+    );
+}
+
+#[test]
+fn assign_ids_keeps_existing_ids_and_appends_new_courses() {
+    let existing = vec![named(0, "MATH 240"), named(1, "CMPT 726")];
+    let mut courses = vec![
+        named(0, "CMPT 120"),
+        named(0, "cmpt 726"),
+        named(0, "MATH 240"),
+        named(0, "CMPT 105"),
+    ];
+    let order = assign_ids(&mut courses, &existing);
+
+    assert_eq!(order, [2, 1, 3, 0]);
+    assert_eq!(
+        codes_and_ids(&courses),
+        [
+            ("MATH 240", 0),
+            ("cmpt 726", 1),
+            ("CMPT 105", 2),
+            ("CMPT 120", 3)
+        ]
+    );
+    assert!(dropped_courses(&courses, &existing).is_empty());
+}
+
+#[test]
+fn dropped_courses_lists_existing_courses_missing_from_the_sources() {
+    let existing = vec![named(0, "CMPT 120"), named(1, "CMPT 225")];
+    let courses = vec![named(0, "cmpt 120")];
+    let dropped: Vec<usize> = dropped_courses(&courses, &existing)
+        .iter()
+        .map(|c| c.id)
+        .collect();
+    assert_eq!(dropped, [1]);
+}
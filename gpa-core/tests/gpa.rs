@@ -0,0 +1,42 @@
+use gpa_core::gpa::GpaTotals;
+
+#[test]
+fn empty_totals_have_zero_gpa() {
+    let totals = GpaTotals::default();
+    assert_eq!(totals.units, 0);
+    assert_eq!(totals.gpa(), 0.0);
+}
+
+#[test]
+fn gpa_is_weighted_by_units() {
+    let totals: GpaTotals = [(3, 4.33), (1, 2.00)].into_iter().collect();
+    assert_eq!(totals.units, 4);
+    assert!((totals.quality_points - 14.99).abs() < 1e-4);
+    assert!((totals.gpa() - 3.7475).abs() < 1e-4);
+}
+
+#[test]
+fn from_grades_uses_the_scale() {
+    let totals = GpaTotals::from_grades([(3, 96.0), (3, 82.0)]);
+    assert_eq!(totals.units, 6);
+    assert!((totals.gpa() - (4.33 + 3.33) / 2.0).abs() < 1e-4);
+}
+
+#[test]
+fn zero_unit_courses_do_not_count() {
+    let totals = GpaTotals::from_grades([(0, 96.0)]);
+    assert_eq!(totals.gpa(), 0.0);
+
+    let totals = GpaTotals::from_grades([(0, 50.0), (3, 90.0)]);
+    assert_eq!(totals.gpa(), 4.0);
+}
+
+#[test]
+fn add_accumulates() {
+    let mut totals = GpaTotals::default();
+    totals.add(3, 4.0);
+    totals.add(3, 3.0);
+    assert_eq!(totals.units, 6);
+    assert_eq!(totals.quality_points, 21.0);
+    assert_eq!(totals.gpa(), 3.5);
+}
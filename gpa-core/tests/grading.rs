@@ -0,0 +1,64 @@
+use gpa_core::grading::{
+    band_for, letter_to_point, point_to_letter, point_to_pa, LETTER_GRADES, SFU_SCALE,
+};
+
+#[test]
+fn band_edges() {
+    assert_eq!(point_to_letter(100.0), "A+");
+    assert_eq!(point_to_letter(95.0), "A+");
+    assert_eq!(point_to_letter(94.99), "A");
+    assert_eq!(point_to_letter(85.0), "A-");
+    assert_eq!(point_to_letter(75.0), "B");
+    assert_eq!(point_to_letter(60.0), "C");
+    assert_eq!(point_to_letter(59.9), "F");
+    assert_eq!(point_to_letter(0.0), "F");
+}
+
+#[test]
+fn grade_points_follow_the_sfu_scale() {
+    assert_eq!(point_to_pa(97.0), 4.33);
+    assert_eq!(point_to_pa(90.0), 4.00);
+    assert_eq!(point_to_pa(86.0), 3.67);
+    assert_eq!(point_to_pa(81.0), 3.33);
+    assert_eq!(point_to_pa(77.0), 3.00);
+    assert_eq!(point_to_pa(70.0), 2.67);
+    assert_eq!(point_to_pa(65.0), 2.33);
+    assert_eq!(point_to_pa(64.0), 2.00);
+    assert_eq!(point_to_pa(12.0), 0.00);
+}
+
+#[test]
+fn out_of_range_percentages_have_no_grade() {
+    for point in [-0.1, 100.1, f32::NAN, f32::INFINITY] {
+        assert_eq!(point_to_letter(point), "");
+        assert_eq!(point_to_pa(point), -1.0);
+        assert!(band_for(point).is_none());
+    }
+}
+
+#[test]
+fn letters_map_to_their_representative_percentage() {
+    let points: Vec<f32> = LETTER_GRADES.iter().map(|l| letter_to_point(l)).collect();
+    assert_eq!(
+        points,
+        [97.0, 93.0, 90.0, 87.0, 83.0, 80.0, 77.0, 73.0, 50.0]
+    );
+}
+
+#[test]
+fn unknown_letters_map_to_zero() {
+    assert_eq!(letter_to_point(""), 0.0);
+    assert_eq!(letter_to_point("D"), 0.0);
+    assert_eq!(letter_to_point("a+"), 0.0);
+}
+
+#[test]
+fn scale_is_ordered_and_covers_every_letter() {
+    let letters: Vec<&str> = SFU_SCALE.iter().map(|band| band.letter).collect();
+    assert_eq!(letters, LETTER_GRADES);
+    for pair in SFU_SCALE.windows(2) {
+        assert!(pair[0].min > pair[1].min);
+        assert!(pair[0].points > pair[1].points);
+    }
+    assert_eq!(SFU_SCALE.last().unwrap().min, 0.0);
+}
@@ -0,0 +1,163 @@
+use gpa_core::prereq::{course_label, validate_plan, PlannedCourse, RequisiteKind};
+use gpa_core::types::{Course, Requisite, Season, Term};
+
+fn course(code: &str, prerequisites: Option<&str>, corequisites: Option<&str>) -> Course {
+    Course {
+        code: code.to_string(),
+        name: format!("{} course", code),
+        prerequisites: prerequisites.map(|r| r.parse().unwrap()),
+        corequisites: corequisites.map(|r| r.parse().unwrap()),
+        ..Course::default()
+    }
+}
+
+fn fall(year: u16) -> Term {
+    Term {
+        year,
+        season: Season::Fall,
+    }
+}
+
+#[test]
+fn parses_and_displays_requisites() {
+    let requisite: Requisite = "CMPT 732 and (CMPT 726 or MATH 240)".parse().unwrap();
+    assert_eq!(requisite.to_string(), "CMPT 732 and (CMPT 726 or MATH 240)");
+
+    // `and` binds tighter than `or`
+    let requisite: Requisite = "A 1 or B 2 AND C 3".parse().unwrap();
+    assert_eq!(
+        requisite,
+        Requisite::Any {
+            any: vec![
+                Requisite::Course("A 1".to_string()),
+                Requisite::All {
+                    all: vec![
+                        Requisite::Course("B 2".to_string()),
+                        Requisite::Course("C 3".to_string()),
+                    ]
+                },
+            ]
+        }
+    );
+}
+
+#[test]
+fn rejects_malformed_requisites() {
+    assert!("".parse::<Requisite>().is_err());
+    assert!("CMPT 732 and".parse::<Requisite>().is_err());
+    assert!("(CMPT 732".parse::<Requisite>().is_err());
+    assert!("CMPT 732)".parse::<Requisite>().is_err());
+}
+
+#[test]
+fn unmet_drops_satisfied_branches() {
+    let requisite: Requisite = "A 1 and B 2 and (C 3 or D 4)".parse().unwrap();
+    let taken = |code: &str| code == "A 1";
+    assert!(!requisite.is_satisfied(&taken));
+    assert_eq!(
+        requisite.unmet(&taken).unwrap().to_string(),
+        "B 2 and (C 3 or D 4)"
+    );
+
+    let taken = |code: &str| code != "B 2";
+    assert_eq!(requisite.unmet(&taken).unwrap().to_string(), "B 2");
+
+    let taken = |_: &str| true;
+    assert!(requisite.unmet(&taken).is_none());
+}
+
+#[test]
+fn prerequisites_must_come_in_an_earlier_term() {
+    let intro = course("CMPT 120", None, None);
+    let advanced = course("CMPT 225", Some("CMPT 120"), None);
+
+    let plan = [
+        PlannedCourse {
+            course: &intro,
+            term: fall(2024),
+        },
+        PlannedCourse {
+            course: &advanced,
+            term: fall(2025),
+        },
+    ];
+    assert!(validate_plan(&plan).is_empty());
+
+    let plan = [
+        PlannedCourse {
+            course: &intro,
+            term: fall(2024),
+        },
+        PlannedCourse {
+            course: &advanced,
+            term: fall(2024),
+        },
+    ];
+    let warnings = validate_plan(&plan);
+    assert_eq!(warnings.len(), 1);
+    assert!(matches!(warnings[0].kind, RequisiteKind::Prerequisite));
+    assert_eq!(warnings[0].course, "CMPT 225");
+    assert_eq!(
+        warnings[0].to_string(),
+        "CMPT 225 is planned for 2024 Fall before its prerequisite CMPT 120"
+    );
+}
+
+#[test]
+fn corequisites_may_share_the_term() {
+    let lecture = course("PHYS 120", None, Some("PHYS 131"));
+    let lab = course("PHYS 131", None, None);
+
+    let plan = [
+        PlannedCourse {
+            course: &lecture,
+            term: fall(2024),
+        },
+        PlannedCourse {
+            course: &lab,
+            term: fall(2024),
+        },
+    ];
+    assert!(validate_plan(&plan).is_empty());
+
+    let plan = [
+        PlannedCourse {
+            course: &lecture,
+            term: fall(2024),
+        },
+        PlannedCourse {
+            course: &lab,
+            term: fall(2025),
+        },
+    ];
+    let warnings = validate_plan(&plan);
+    assert_eq!(warnings.len(), 1);
+    assert!(matches!(warnings[0].kind, RequisiteKind::Corequisite));
+}
+
+#[test]
+fn requisites_match_codes_case_insensitively() {
+    let intro = course("cmpt 120", None, None);
+    let advanced = course("CMPT 225", Some("CMPT 120"), None);
+    let plan = [
+        PlannedCourse {
+            course: &intro,
+            term: fall(2023),
+        },
+        PlannedCourse {
+            course: &advanced,
+            term: fall(2024),
+        },
+    ];
+    assert!(validate_plan(&plan).is_empty());
+}
+
+#[test]
+fn labels_prefer_the_code() {
+    assert_eq!(course_label(&course("CMPT 120", None, None)), "CMPT 120");
+    let unnamed = Course {
+        name: "Independent study".to_string(),
+        ..Course::default()
+    };
+    assert_eq!(course_label(&unnamed), "Independent study");
+}
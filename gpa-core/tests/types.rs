@@ -0,0 +1,109 @@
+use gpa_core::types::{Course, Enrollment, Offering, Requisite, Season, Section, Term};
+
+fn term(year: u16, season: Season) -> Term {
+    Term { year, season }
+}
+
+#[test]
+fn terms_parse_in_either_order() {
+    assert_eq!("2024 Fall".parse(), Ok(term(2024, Season::Fall)));
+    assert_eq!("spring 2025".parse(), Ok(term(2025, Season::Spring)));
+    assert!("2024".parse::<Term>().is_err());
+    assert!("Fall".parse::<Term>().is_err());
+}
+
+#[test]
+fn terms_reject_extra_or_repeated_parts() {
+    assert!("Fall 2024 2025".parse::<Term>().is_err());
+    assert!("2024 Fall (Graduate)".parse::<Term>().is_err());
+    assert!("Fall Spring 2024".parse::<Term>().is_err());
+    assert!("2024 2025".parse::<Term>().is_err());
+    assert!("Fall Fall".parse::<Term>().is_err());
+}
+
+#[test]
+fn terms_order_chronologically() {
+    let mut terms = vec![
+        term(2025, Season::Spring),
+        term(2024, Season::Fall),
+        term(2024, Season::Summer),
+        term(2024, Season::Spring),
+    ];
+    terms.sort();
+    assert_eq!(
+        terms,
+        vec![
+            term(2024, Season::Spring),
+            term(2024, Season::Summer),
+            term(2024, Season::Fall),
+            term(2025, Season::Spring),
+        ]
+    );
+}
+
+#[test]
+fn terms_serialize_as_strings() {
+    let json = serde_json::to_string(&term(2024, Season::Fall)).unwrap();
+    assert_eq!(json, "\"2024 Fall\"");
+    assert_eq!(
+        serde_json::from_str::<Term>(&json).unwrap(),
+        term(2024, Season::Fall)
+    );
+}
+
+#[test]
+fn requisites_deserialize_from_catalog_notation() {
+    let requisite: Requisite =
+        serde_json::from_str(r#"{"all": ["CMPT 732", {"any": ["CMPT 726", "MATH 240"]}]}"#)
+            .unwrap();
+    assert_eq!(
+        requisite,
+        Requisite::All {
+            all: vec![
+                Requisite::Course("CMPT 732".to_string()),
+                Requisite::Any {
+                    any: vec![
+                        Requisite::Course("CMPT 726".to_string()),
+                        Requisite::Course("MATH 240".to_string()),
+                    ]
+                },
+            ]
+        }
+    );
+}
+
+#[test]
+fn instructor_comes_from_the_enrolled_section() {
+    let fall = term(2024, Season::Fall);
+    let course = Course {
+        teacher: "Default".to_string(),
+        offerings: vec![Offering {
+            term: fall,
+            sections: vec![Section {
+                code: "D100".to_string(),
+                instructor: "Section Lead".to_string(),
+                meeting_times: vec![],
+                campus: "Burnaby".to_string(),
+            }],
+        }],
+        ..Course::default()
+    };
+    let enrolled = |section: &str, term| Enrollment {
+        term,
+        section: section.to_string(),
+    };
+
+    assert_eq!(course.instructor_for(None), "Default");
+    assert_eq!(
+        course.instructor_for(Some(&enrolled("D100", fall))),
+        "Section Lead"
+    );
+    assert_eq!(
+        course.instructor_for(Some(&enrolled("D200", fall))),
+        "Default"
+    );
+    assert_eq!(
+        course.instructor_for(Some(&enrolled("D100", term(2025, Season::Spring)))),
+        "Default"
+    );
+}
@@ -0,0 +1,54 @@
+use gpa_core::validation::{
+    check_name, course_problems, parse_grade, parse_unit, Field, FieldError,
+};
+
+fn field(result: Result<impl Sized, FieldError>) -> Option<Field> {
+    result.err().map(|err| err.field)
+}
+
+#[test]
+fn grades_must_be_numbers_in_range() {
+    assert_eq!(parse_grade("87.5"), Ok(87.5));
+    assert_eq!(parse_grade(" 100 "), Ok(100.0));
+    assert_eq!(parse_grade("0"), Ok(0.0));
+    assert_eq!(field(parse_grade("")), Some(Field::Grade));
+    assert_eq!(field(parse_grade("A")), Some(Field::Grade));
+    assert_eq!(field(parse_grade("100.5")), Some(Field::Grade));
+    assert_eq!(field(parse_grade("-1")), Some(Field::Grade));
+    assert_eq!(field(parse_grade("NaN")), Some(Field::Grade));
+}
+
+#[test]
+fn units_must_be_whole_numbers_in_range() {
+    assert_eq!(parse_unit("3"), Ok(3));
+    assert_eq!(parse_unit("30"), Ok(30));
+    assert_eq!(field(parse_unit("31")), Some(Field::Unit));
+    assert_eq!(field(parse_unit("1000000000")), Some(Field::Unit));
+    assert_eq!(field(parse_unit("0")), Some(Field::Unit));
+    assert_eq!(field(parse_unit("-3")), Some(Field::Unit));
+    assert_eq!(field(parse_unit("1.5")), Some(Field::Unit));
+    assert_eq!(field(parse_unit("")), Some(Field::Unit));
+}
+
+#[test]
+fn names_must_not_be_blank() {
+    assert!(check_name("Machine Learning").is_ok());
+    assert_eq!(field(check_name("   ")), Some(Field::Name));
+}
+
+#[test]
+fn course_problems_lists_every_bad_field() {
+    assert!(course_problems("CMPT 726", 3, 91.0).is_empty());
+
+    let fields: Vec<Field> = course_problems("", 0, 120.0)
+        .into_iter()
+        .map(|problem| problem.field)
+        .collect();
+    assert_eq!(fields, [Field::Grade, Field::Unit, Field::Name]);
+}
+
+#[test]
+fn messages_are_user_facing() {
+    let err = parse_grade("abc").unwrap_err();
+    assert_eq!(err.to_string(), "Grade must be a number");
+}
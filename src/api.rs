@@ -1,45 +1,108 @@
 // src/api.rs
-use crate::types::Course;
-use anyhow::{Error, Ok};
 use gloo::net::http::Request;
+use gpa_core::types::Course;
+use std::fmt;
 use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 
-pub fn get_courses(callback: Callback<Result<Vec<Course>, Error>>) {
-    spawn_local(async move {
-        let result = async {
-            let response = Request::get("/courses/courses.json")
-                .send()
-                .await
-                .map_err(Error::from)?;
+/// Where the catalog is served from. Relative course images resolve against this directory.
+pub const CATALOG_DIR: &str = "/courses";
+const CATALOG_URL: &str = "/courses/courses.json";
 
-            // Parse JSON as Vec<Course>
-            let courses = response.json::<Vec<Course>>().await.map_err(Error::from)?;
+/// Why the catalog or a course could not be fetched.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ApiError {
+    /// The request did not get a response, e.g. when offline.
+    Network(String),
+    /// The server answered with a non-success status.
+    Status { code: u16, text: String },
+    /// The catalog is not valid JSON or does not match the course format.
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// No course in the catalog has this id.
+    NotFound(usize),
+}
 
-            Ok(courses)
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::Network(detail) => write!(
+                f,
+                "Could not reach the server ({}). Check your connection and try again.",
+                detail
+            ),
+            ApiError::Status { code, text } => {
+                write!(f, "The server could not send the course list ({}", code)?;
+                if !text.is_empty() {
+                    write!(f, " {}", text)?;
+                }
+                write!(f, ").")
+            }
+            ApiError::Parse {
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "The course list is malformed at line {}, column {}: {}.",
+                line, column, message
+            ),
+            ApiError::NotFound(id) => write!(f, "There is no course with id {}.", id),
         }
-        .await;
+    }
+}
 
-        // pass the res
-        callback.emit(result);
-    });
+impl std::error::Error for ApiError {}
+
+async fn fetch_catalog() -> Result<Vec<Course>, ApiError> {
+    let response = Request::get(CATALOG_URL)
+        .send()
+        .await
+        .map_err(|err| ApiError::Network(err.to_string()))?;
+    if !response.ok() {
+        return Err(ApiError::Status {
+            code: response.status(),
+            text: response.status_text(),
+        });
+    }
+
+    // Parse the text ourselves so errors keep their position in the file
+    let text = response
+        .text()
+        .await
+        .map_err(|err| ApiError::Network(err.to_string()))?;
+    serde_json::from_str::<Vec<Course>>(&text).map_err(|err| {
+        let (line, column) = (err.line(), err.column());
+        let message = err.to_string();
+        let suffix = format!(" at line {} column {}", line, column);
+        ApiError::Parse {
+            line,
+            column,
+            message: message
+                .strip_suffix(&suffix)
+                .unwrap_or(&message)
+                .to_string(),
+        }
+    })
 }
 
-pub fn get_course(id: usize, callback: Callback<Result<Course, Error>>) {
+pub fn get_courses(callback: Callback<Result<Vec<Course>, ApiError>>) {
     spawn_local(async move {
-        let result = async {
-            let response = Request::get("/courses/courses.json")
-                .send()
-                .await
-                .map_err(Error::from)?;
-
-            // Parse JSON as Vec<Course>
-            let courses = response.json::<Vec<Course>>().await.map_err(Error::from)?;
-            let course = courses[id].clone();
-            Ok(course)
-        }
-        .await;
+        callback.emit(fetch_catalog().await);
+    });
+}
 
+pub fn get_course(id: usize, callback: Callback<Result<Course, ApiError>>) {
+    spawn_local(async move {
+        let result = fetch_catalog().await.and_then(|courses| {
+            courses
+                .into_iter()
+                .find(|course| course.id == id)
+                .ok_or(ApiError::NotFound(id))
+        });
         callback.emit(result);
     })
 }
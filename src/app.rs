@@ -1,8 +1,16 @@
 // src/app.rs
-use crate::app_state::{AppState, AppStateContext};
+use crate::app_state::{
+    AppState, AppStateAction, AppStateContext, Settings, SettingsContext, SETTINGS_KEY,
+};
 use crate::components::GPAOverview;
-use crate::pages::{CourseDetail, Home};
+use crate::logging::{self, error};
+use crate::pages::{CourseDetail, DataPage, Home, Report, SharedView, UserCourseDetail};
 use crate::route::Route;
+use crate::storage::{Storage, StoreName};
+use crate::sync::{SyncMessage, TabSync};
+use gloo::events::EventListener;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 use yew_router::prelude::*;
 
@@ -10,14 +18,162 @@ use yew_router::prelude::*;
 pub fn app() -> Html {
     // Now you can use hooks inside this function component
     let app_state = use_reducer(AppState::default);
+    let settings = use_state(Settings::default);
+    let storage = use_state(|| None::<Storage>);
+    let sync = use_memo((), |_| TabSync::open());
 
-    html! {
+    // Ctrl+Z undoes the last edit; Ctrl+Shift+Z or Ctrl+Y redoes it (Cmd on macOS)
+    {
+        let app_state = app_state.clone();
+        use_effect_with((), move |_| {
+            let document = gloo::utils::document();
+            let listener = EventListener::new(&document, "keydown", move |event| {
+                let Some(event) = event.dyn_ref::<web_sys::KeyboardEvent>() else {
+                    return;
+                };
+                if !(event.ctrl_key() || event.meta_key()) || keeps_native_undo(event.target()) {
+                    return;
+                }
+                let action = match event.key().to_lowercase().as_str() {
+                    "z" if event.shift_key() => AppStateAction::Redo,
+                    "z" => AppStateAction::Undo,
+                    "y" => AppStateAction::Redo,
+                    _ => return,
+                };
+                event.prevent_default();
+                app_state.dispatch(action);
+            });
+            move || drop(listener)
+        });
+    }
+
+    // Open storage and restore the previous session before anything is rendered, then
+    // merge changes made in other tabs; merging earlier could let the stored session
+    // overwrite them
+    {
+        let app_state = app_state.clone();
+        let settings = settings.clone();
+        let storage = storage.clone();
+        let sync = sync.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                let backend = Storage::open().await;
+                if let Some(saved) = AppState::load(&backend).await {
+                    app_state.dispatch(AppStateAction::Restore(saved));
+                }
+                match backend
+                    .load::<Settings>(StoreName::Settings, SETTINGS_KEY)
+                    .await
+                {
+                    Ok(Some(saved)) => settings.set(saved),
+                    Ok(None) => {}
+                    Err(err) => error!("Could not load settings: {}", err),
+                }
+                storage.set(Some(backend));
+                if let Some(sync) = sync.as_ref() {
+                    sync.listen(move |message, base| match message {
+                        SyncMessage::State(incoming) => {
+                            app_state.dispatch(AppStateAction::Merge(incoming, base))
+                        }
+                        SyncMessage::Settings(synced) => settings.set(synced),
+                    });
+                }
+            });
+        });
+    }
+
+    // Save and tell other tabs after every change made here; changes that came from
+    // another tab were already saved there
+    {
+        let storage = (*storage).clone();
+        let sync = sync.clone();
+        use_effect_with((*app_state).clone(), move |state| {
+            let message = SyncMessage::State(state.clone());
+            let from_other_tab = sync
+                .as_ref()
+                .as_ref()
+                .is_some_and(|s| s.take_echo(&message));
+            if let (Some(storage), false) = (storage, from_other_tab) {
+                state.save(&storage);
+                if let Some(sync) = sync.as_ref() {
+                    sync.publish(&message);
+                }
+            }
+        });
+    }
+    {
+        let storage = (*storage).clone();
+        let sync = sync.clone();
+        use_effect_with((*settings).clone(), move |settings| {
+            logging::set_debug(settings.debug_logging);
+            if let Some(body) = gloo::utils::document().body() {
+                body.set_class_name(&settings.theme);
+            }
+            let message = SyncMessage::Settings(settings.clone());
+            let from_other_tab = sync
+                .as_ref()
+                .as_ref()
+                .is_some_and(|s| s.take_echo(&message));
+            if let (Some(storage), false) = (storage, from_other_tab) {
+                if let Some(sync) = sync.as_ref() {
+                    sync.publish(&message);
+                }
+                let settings = settings.clone();
+                spawn_local(async move {
+                    if let Err(err) = storage
+                        .save(StoreName::Settings, SETTINGS_KEY, &settings)
+                        .await
+                    {
+                        error!("Could not save settings: {}", err);
+                    }
+                });
+            }
+        });
+    }
+
+    let Some(storage) = (*storage).clone() else {
+        return html! {
+            <div class="loading_spinner_container">
+                <div class="loading_spinner"></div>
+                <div class="loading_spinner_text">{"Loading ..."}</div>
+            </div>
+        };
+    };
 
+    html! {
+            <ContextProvider<Storage> context={storage}>
+            <ContextProvider<SettingsContext> context={settings}>
             <ContextProvider<AppStateContext> context={app_state}>
                 <BrowserRouter>
                     <Switch<Route> render={switch} />
                 </BrowserRouter>
             </ContextProvider<AppStateContext>>
+            </ContextProvider<SettingsContext>>
+            </ContextProvider<Storage>>
+    }
+}
+
+/// Marks a text input whose edits are recorded in the app history, so Ctrl+Z in it
+/// undoes through the app instead of the browser.
+const APP_UNDO_ATTRIBUTE: &str = "data-app-undo";
+
+/// Whether the shortcut should be left to the browser: textareas and text inputs that
+/// are not recorded in the app history keep their own undo.
+fn keeps_native_undo(target: Option<web_sys::EventTarget>) -> bool {
+    let Some(target) = target else {
+        return false;
+    };
+    if target.dyn_ref::<web_sys::HtmlTextAreaElement>().is_some() {
+        return true;
+    }
+    match target.dyn_ref::<web_sys::HtmlInputElement>() {
+        Some(input) => {
+            !matches!(
+                input.type_().as_str(),
+                "checkbox" | "radio" | "button" | "submit" | "file"
+            ) && !input.has_attribute(APP_UNDO_ATTRIBUTE)
+        }
+        None => false,
     }
 }
 
@@ -27,10 +183,14 @@ fn switch(routes: Route) -> Html {
         Route::CourseDetail { id } => {
             html! {<CourseDetail id={ id }/>}
         }
+        Route::UserCourseDetail { id } => html! {<UserCourseDetail id={ id }/>},
         Route::GPAView => {
             html! {
                 <GPAOverview />
             }
         }
+        Route::Data => html! {<DataPage/>},
+        Route::Shared => html! {<SharedView/>},
+        Route::Report => html! {<Report/>},
     }
 }
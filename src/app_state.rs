@@ -1,203 +1,925 @@
 // app_state.rs
-use crate::types::Course;
+use crate::history::History;
+use crate::logging::{error, warn};
+use crate::storage::{Storage, StoreName};
+use gpa_core::grading::{letter_to_point, point_to_letter};
+use gpa_core::prereq::{course_label, PlannedCourse};
+use gpa_core::types::{Course, Enrollment};
+use gpa_core::validation::{course_problems, FieldError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
 use std::rc::Rc;
+use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 
-macro_rules! update_with_rc {
-    ($self:ident, { $( $field:ident ),* }, { $( $non_rc_field:ident : $value:expr ),* }) => {
-        Rc::new(Self {
-            $(
-                $field: Rc::clone(&$self.$field),
-            )*
-            $(
-                $non_rc_field: $value,
-            )*
-        })
-    };
-}
-
-#[derive(Clone, PartialEq)]
-pub enum AppStateValue {
-    Courses(Vec<Course>),
-    Grades(Vec<f32>),
-    Checks(Vec<bool>),
-    UserCourses(Vec<Course>),
-    UserGrades(Vec<f32>),
-    UserChecks(Vec<bool>),
-    GradesLetter(Vec<String>),
-    UserGradesLetter(Vec<String>),
-}
-
-#[derive(Clone, PartialEq)]
+/// Identifies a course entry. Catalog entries use the catalog's id; user courses get a
+/// number when they are added that is never reused, so it survives catalog reloads.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub enum EntryId {
+    Catalog(usize),
+    User(usize),
+}
+
+/// A course together with everything the user entered for it.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct CourseEntry {
+    pub id: EntryId,
+    pub course: Course,
+    /// Percentage score.
+    pub grade: f32,
+    pub letter: String,
+    /// Whether the course counts towards the GPA.
+    pub included: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enrollment: Option<Enrollment>,
+    /// The student's own notes on the course.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub notes: String,
+}
+
+impl CourseEntry {
+    pub fn new(id: EntryId, course: Course) -> Self {
+        Self {
+            id,
+            course,
+            grade: 0.0,
+            letter: String::new(),
+            included: false,
+            enrollment: None,
+            notes: String::new(),
+        }
+    }
+
+    pub fn is_user_course(&self) -> bool {
+        matches!(self.id, EntryId::User(_))
+    }
+
+    /// Values that keep the course out of the GPA until they are fixed.
+    pub fn problems(&self) -> Vec<FieldError> {
+        course_problems(&self.course.name, self.course.unit, self.grade)
+    }
+}
+
+/// Free-text details that can be edited on a user course.
+#[derive(Clone, Copy, PartialEq)]
+pub enum DetailField {
+    Teacher,
+    Description,
+    Image,
+    Notes,
+}
+
+/// The kind of edit that consecutive actions on one entry coalesce by.
+#[derive(Clone, Copy, PartialEq)]
+enum EditKind {
+    Grade,
+    Name,
+    Unit,
+    Detail(DetailField),
+}
+
+/// What an undo step restores: the entries and the next user course number.
+type Snapshot = (Rc<Vec<CourseEntry>>, usize);
+
+/// Catalog entries in catalog order, followed by user courses in the order they were added.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppState {
-    pub courses: Rc<Vec<Course>>,
-    pub grades: Rc<Vec<f32>>,
-    pub checks: Rc<Vec<bool>>,
-    pub user_courses: Rc<Vec<Course>>, // User-added courses
-    pub user_grades: Rc<Vec<f32>>,
-    pub user_checks: Rc<Vec<bool>>,
-    pub grades_letter: Rc<Vec<String>>,
-    pub user_grades_letter: Rc<Vec<String>>,
+    pub entries: Rc<Vec<CourseEntry>>,
+    /// Number for the next user course.
+    pub next_user_id: usize,
+    /// Undo/redo steps for this session; never saved, shared or compared.
+    #[serde(skip)]
+    history: Rc<History<Snapshot, (EntryId, EditKind)>>,
 }
 
-impl Default for AppState {
-    fn default() -> Self {
+impl PartialEq for AppState {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries == other.entries && self.next_user_id == other.next_user_id
+    }
+}
+
+const CURRENT_SNAPSHOT: &str = "current";
+const STATE_VERSION: u32 = 2;
+
+#[derive(Serialize)]
+struct SavedStateRef<'a> {
+    version: u32,
+    state: &'a AppState,
+}
+
+#[derive(Deserialize)]
+struct SavedState {
+    version: u32,
+    state: Value,
+}
+
+/// Version 1 kept each field in its own index-aligned vector.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct StateV1 {
+    courses: Vec<Course>,
+    grades: Vec<f32>,
+    checks: Vec<bool>,
+    user_courses: Vec<Course>,
+    user_grades: Vec<f32>,
+    user_checks: Vec<bool>,
+    grades_letter: Vec<String>,
+    user_grades_letter: Vec<String>,
+    enrollments: BTreeMap<usize, Enrollment>,
+}
+
+impl From<StateV1> for AppState {
+    fn from(old: StateV1) -> Self {
+        let entry = |id,
+                     course: &Course,
+                     grade: Option<&f32>,
+                     letter: Option<&String>,
+                     check: Option<&bool>| {
+            CourseEntry {
+                id,
+                course: course.clone(),
+                grade: grade.copied().unwrap_or_default(),
+                letter: letter.cloned().unwrap_or_default(),
+                included: check.copied().unwrap_or_default(),
+                enrollment: old.enrollments.get(&course.id).cloned(),
+                notes: String::new(),
+            }
+        };
+        let catalog = old.courses.iter().enumerate().map(|(i, course)| {
+            entry(
+                EntryId::Catalog(course.id),
+                course,
+                old.grades.get(i),
+                old.grades_letter.get(i),
+                old.checks.get(i),
+            )
+        });
+        let user = old.user_courses.iter().enumerate().map(|(i, course)| {
+            let mut entry = entry(
+                EntryId::User(i),
+                course,
+                old.user_grades.get(i),
+                old.user_grades_letter.get(i),
+                old.user_checks.get(i),
+            );
+            entry.course.id = i;
+            entry
+        });
+        let entries = catalog.chain(user).collect();
         Self {
-            courses: Rc::new(vec![]),
-            grades: Rc::new(vec![]),
-            grades_letter: Rc::new(vec![]),
-            checks: Rc::new(vec![]),
-            user_courses: Rc::new(vec![]),
-            user_grades: Rc::new(vec![]),
-            user_grades_letter: Rc::new(vec![]),
-            user_checks: Rc::new(vec![]),
+            entries: Rc::new(entries),
+            next_user_id: old.user_courses.len(),
+            history: Rc::default(),
         }
     }
 }
 
+impl AppState {
+    /// Reads a state saved by this or any earlier version of the app.
+    pub(crate) fn from_saved(version: u32, state: Value) -> Result<Self, String> {
+        match version {
+            1 => serde_json::from_value::<StateV1>(state)
+                .map(Self::from)
+                .map_err(|err| err.to_string()),
+            STATE_VERSION => serde_json::from_value(state).map_err(|err| err.to_string()),
+            _ => Err(format!("unknown state version {}", version)),
+        }
+    }
+
+    /// The state saved by the previous session, if there is a readable one.
+    pub async fn load(storage: &Storage) -> Option<Self> {
+        let saved = match storage
+            .load::<SavedState>(StoreName::Snapshots, CURRENT_SNAPSHOT)
+            .await
+        {
+            Ok(saved) => saved?,
+            Err(err) => {
+                warn!("Ignoring unreadable saved state: {}", err);
+                return None;
+            }
+        };
+        match Self::from_saved(saved.version, saved.state) {
+            Ok(state) => Some(state),
+            Err(err) => {
+                warn!("Ignoring saved state: {}", err);
+                None
+            }
+        }
+    }
+
+    /// Saves the state in the background; failures are logged so editing keeps working.
+    pub fn save(&self, storage: &Storage) {
+        let storage = storage.clone();
+        let state = self.clone();
+        spawn_local(async move {
+            let saved = SavedStateRef {
+                version: STATE_VERSION,
+                state: &state,
+            };
+            if let Err(err) = storage
+                .save(StoreName::Snapshots, CURRENT_SNAPSHOT, &saved)
+                .await
+            {
+                error!("Could not save state: {}", err);
+            }
+        });
+    }
+
+    pub fn entry(&self, id: EntryId) -> Option<&CourseEntry> {
+        self.entries.iter().find(|entry| entry.id == id)
+    }
+
+    pub fn entry_mut(&mut self, id: EntryId) -> Option<&mut CourseEntry> {
+        Rc::make_mut(&mut self.entries)
+            .iter_mut()
+            .find(|entry| entry.id == id)
+    }
+
+    pub fn catalog_entries(&self) -> impl Iterator<Item = &CourseEntry> {
+        self.entries.iter().filter(|entry| !entry.is_user_course())
+    }
+
+    pub fn user_entries(&self) -> impl Iterator<Item = &CourseEntry> {
+        self.entries.iter().filter(|entry| entry.is_user_course())
+    }
+
+    /// Entries that count towards the GPA.
+    pub fn included(&self) -> impl Iterator<Item = &CourseEntry> {
+        self.entries.iter().filter(|entry| entry.included)
+    }
+
+    /// The catalog as it was last loaded.
+    pub fn catalog(&self) -> Vec<Course> {
+        self.catalog_entries()
+            .map(|entry| entry.course.clone())
+            .collect()
+    }
+
+    /// Appends a user course and returns its id.
+    pub fn add_user_course(&mut self, mut course: Course) -> EntryId {
+        let id = EntryId::User(self.next_user_id);
+        course.id = self.next_user_id;
+        self.next_user_id += 1;
+        Rc::make_mut(&mut self.entries).push(CourseEntry::new(id, course));
+        id
+    }
+
+    /// Removes a user course and returns it; catalog courses cannot be removed.
+    pub fn remove_user_course(&mut self, id: EntryId) -> Option<CourseEntry> {
+        let index = self
+            .entries
+            .iter()
+            .position(|entry| entry.id == id && entry.is_user_course())?;
+        Some(Rc::make_mut(&mut self.entries).remove(index))
+    }
+
+    /// Inserts a copy of a user course right after it, under a new id, and returns that id.
+    pub fn duplicate_user_course(&mut self, id: EntryId) -> Option<EntryId> {
+        let index = self
+            .entries
+            .iter()
+            .position(|entry| entry.id == id && entry.is_user_course())?;
+        let mut copy = self.entries[index].clone();
+        copy.id = EntryId::User(self.next_user_id);
+        copy.course.id = self.next_user_id;
+        copy.course.name = format!("{} (copy)", copy.course.name);
+        self.next_user_id += 1;
+        let new_id = copy.id;
+        Rc::make_mut(&mut self.entries).insert(index + 1, copy);
+        Some(new_id)
+    }
+
+    /// Moves a user course `offset` places among the user courses; it stops at either end.
+    pub fn move_user_course(&mut self, id: EntryId, offset: isize) {
+        let first_user = self
+            .entries
+            .iter()
+            .position(CourseEntry::is_user_course)
+            .unwrap_or(self.entries.len());
+        let Some(index) = self
+            .entries
+            .iter()
+            .position(|entry| entry.id == id && entry.is_user_course())
+        else {
+            return;
+        };
+        let target = index
+            .saturating_add_signed(offset)
+            .clamp(first_user, self.entries.len() - 1);
+        if target != index {
+            let entries = Rc::make_mut(&mut self.entries);
+            let entry = entries.remove(index);
+            entries.insert(target, entry);
+        }
+    }
+
+    /// Replaces the catalog with a freshly fetched one, carrying grades, inclusion and
+    /// enrollments over to the course they belong to even if the catalog was reordered,
+    /// extended or trimmed since they were saved. User courses are kept as they are.
+    pub(crate) fn with_catalog(&self, courses: Vec<Course>) -> Self {
+        let mut entries: Vec<CourseEntry> = courses
+            .into_iter()
+            .map(|course| {
+                let id = EntryId::Catalog(course.id);
+                match self
+                    .catalog_entries()
+                    .find(|old| same_course(&old.course, &course))
+                {
+                    Some(old) => CourseEntry {
+                        id,
+                        course,
+                        ..old.clone()
+                    },
+                    None => CourseEntry::new(id, course),
+                }
+            })
+            .collect();
+        entries.extend(self.user_entries().cloned());
+
+        Self {
+            entries: Rc::new(entries),
+            next_user_id: self.next_user_id,
+            history: self.history.clone(),
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        (self.entries.clone(), self.next_user_id)
+    }
+
+    /// This state with `snapshot`'s entries and the given history.
+    fn with_snapshot(
+        &self,
+        (entries, next_user_id): Snapshot,
+        history: History<Snapshot, (EntryId, EditKind)>,
+    ) -> Self {
+        Self {
+            entries,
+            next_user_id,
+            history: Rc::new(history),
+        }
+    }
+
+    /// Included courses (catalog and user-added) that have a term chosen, in term order.
+    pub fn plan(&self) -> Vec<PlannedCourse<'_>> {
+        let mut plan: Vec<PlannedCourse> = self
+            .included()
+            .filter_map(|entry| {
+                Some(PlannedCourse {
+                    course: &entry.course,
+                    term: entry.enrollment.as_ref()?.term,
+                })
+            })
+            .collect();
+        plan.sort_by_key(|planned| planned.term);
+        plan
+    }
+
+    /// Merges a state from another tab entry by entry. `base` holds the entries both tabs
+    /// last had in common: an entry changed or removed only here keeps this tab's version,
+    /// anything else takes the incoming one. A user course both tabs added under the same
+    /// number keeps the incoming one there and the local one moves to a new number.
+    pub fn merged(&self, base: &[CourseEntry], incoming: &AppState) -> AppState {
+        let in_base = |id| base.iter().find(|entry: &&CourseEntry| entry.id == id);
+        let mut next_user_id = self.next_user_id.max(incoming.next_user_id);
+        let mut entries = vec![];
+        let mut renumbered = vec![];
+
+        for theirs in incoming.entries.iter() {
+            let before = in_base(theirs.id);
+            let ours = self.entry(theirs.id);
+            if ours == before || Some(theirs) != before {
+                if let (None, Some(ours)) = (before, ours) {
+                    if ours != theirs {
+                        renumbered.push(ours.clone());
+                    }
+                }
+                // Unchanged here, or changed in both and the incoming edit is the later one
+                entries.push(theirs.clone());
+            } else if let Some(ours) = ours {
+                entries.push(ours.clone());
+            }
+        }
+        for ours in self.entries.iter() {
+            // Added here, or removed there after it was edited here
+            if incoming.entry(ours.id).is_none() && in_base(ours.id) != Some(ours) {
+                entries.push(ours.clone());
+            }
+        }
+        for mut ours in renumbered {
+            ours.id = EntryId::User(next_user_id);
+            ours.course.id = next_user_id;
+            next_user_id += 1;
+            entries.push(ours);
+        }
+
+        AppState {
+            entries: Rc::new(entries),
+            next_user_id,
+            history: self.history.clone(),
+        }
+    }
+
+    /// Codes of every included course, regardless of term.
+    pub fn included_codes(&self) -> Vec<String> {
+        self.included()
+            .map(|entry| course_label(&entry.course).to_string())
+            .collect()
+    }
+}
+
 pub enum AppStateAction {
-    UpdateAll {
-        courses: Rc<Vec<Course>>,
-        grades: Rc<Vec<f32>>,
-        grades_letter: Rc<Vec<String>>,
-        checks: Rc<Vec<bool>>,
-        user_courses: Rc<Vec<Course>>,
-        user_grades: Rc<Vec<f32>>,
-        user_grades_letter: Rc<Vec<String>>,
-        user_checks: Rc<Vec<bool>>,
-    },
-    UpdateAllNonUser {
-        courses: Rc<Vec<Course>>,
-        grades: Rc<Vec<f32>>,
-        grades_letter: Rc<Vec<String>>,
-        checks: Rc<Vec<bool>>,
-    },
-    UpdateAllUser {
-        user_courses: Rc<Vec<Course>>,
-        user_grades: Rc<Vec<f32>>,
-        user_grades_letter: Rc<Vec<String>>,
-        user_checks: Rc<Vec<bool>>,
-    },
-
-    UpdateSingle {
-        values: AppStateValue,
-    },
+    LoadCatalog(Vec<Course>),
+    /// Takes over a state loaded from storage, without an undo step.
+    Restore(AppState),
+    /// Merges a state from another tab against the entries the tabs last had in common
+    /// (see `AppState::merged`); without those, the incoming state wins. Undo steps taken
+    /// before it are dropped, since undoing them would silently revert the other tab's
+    /// changes.
+    Merge(AppState, Option<Rc<Vec<CourseEntry>>>),
+    /// Replaces everything (imports, backups) as one step that can be undone.
+    Replace(AppState),
+    Undo,
+    Redo,
+    /// Sets the percentage and the letter that goes with it.
+    SetGrade(EntryId, f32),
+    /// Sets the letter and the percentage that stands for it.
+    SetLetter(EntryId, String),
+    ToggleIncluded(EntryId),
+    SetEnrollment(EntryId, Option<Enrollment>),
+    AddUserCourse(Course),
+    RemoveUserCourse(EntryId),
+    /// Copies a user course, placing the copy right after it.
+    DuplicateUserCourse(EntryId),
+    /// Moves a user course up (negative) or down (positive) among the user courses.
+    MoveUserCourse(EntryId, isize),
+    SetName(EntryId, String),
+    SetUnit(EntryId, i32),
+    SetDetail(EntryId, DetailField, String),
+}
+
+/// Catalog courses are matched by code when both have one, otherwise by name.
+pub(crate) fn same_course(old: &Course, new: &Course) -> bool {
+    if !old.code.is_empty() && !new.code.is_empty() {
+        old.code.eq_ignore_ascii_case(&new.code)
+    } else {
+        old.name == new.name
+    }
 }
 
 impl Reducible for AppState {
     type Action = AppStateAction;
     fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut state = (*self).clone();
+        let mut coalesce = None;
         match action {
-            AppStateAction::UpdateAll {
-                courses,
-                grades,
-                grades_letter,
-                checks,
-                user_courses,
-                user_grades,
-                user_grades_letter,
-                user_checks,
-            } => Rc::new(Self {
-                courses,
-                grades,
-                grades_letter,
-                checks,
-                user_courses,
-                user_grades,
-                user_grades_letter,
-                user_checks,
-            }),
-
-            AppStateAction::UpdateAllNonUser {
-                courses,
-                grades,
-                grades_letter,
-                checks,
-            } => Rc::new(Self {
-                courses,
-                grades,
-                grades_letter,
-                checks,
-                user_courses: Rc::clone(&self.user_courses),
-                user_grades: Rc::clone(&self.user_grades),
-                user_grades_letter: Rc::clone(&self.user_grades_letter),
-                user_checks: Rc::clone(&self.user_checks),
-            }),
-            AppStateAction::UpdateAllUser {
-                user_courses,
-                user_grades,
-                user_grades_letter,
-                user_checks,
-            } => Rc::new(Self {
-                courses: Rc::clone(&self.courses),
-                grades: Rc::clone(&self.grades),
-                grades_letter: Rc::clone(&self.grades_letter),
-                checks: Rc::clone(&self.checks),
-                user_courses,
-                user_grades,
-                user_grades_letter,
-                user_checks,
-            }),
-            AppStateAction::UpdateSingle { values } => match values {
-                AppStateValue::Courses(courses) => {
-                    update_with_rc!(
-                        self,
-                        { grades, grades_letter, checks, user_courses, user_grades, user_grades_letter, user_checks },
-                        { courses: Rc::new(courses) }
-                    )
+            AppStateAction::LoadCatalog(courses) => {
+                // Steps taken against the old catalog could bring back courses it dropped
+                let mut loaded = self.with_catalog(courses);
+                if loaded != *self {
+                    loaded.history = Rc::default();
                 }
-                AppStateValue::Grades(grades) => {
-                    update_with_rc!(
-                        self,
-                        { courses, grades_letter, checks, user_courses, user_grades, user_grades_letter, user_checks },
-                        { grades: Rc::new(grades) }
-                    )
+                return Rc::new(loaded);
+            }
+            AppStateAction::Restore(restored) => {
+                return Rc::new(Self {
+                    history: self.history.clone(),
+                    ..restored
+                })
+            }
+            AppStateAction::Merge(incoming, base) => {
+                let base = base.unwrap_or_else(|| self.entries.clone());
+                let merged = self.merged(&base, &incoming);
+                if merged == *self {
+                    return self;
                 }
-                AppStateValue::GradesLetter(grades_letter) => {
-                    update_with_rc!(
-                        self,
-                        { courses, grades, checks, user_courses, user_grades, user_grades_letter, user_checks },
-                        { grades_letter: Rc::new(grades_letter) }
-                    )
+                return Rc::new(Self {
+                    history: Rc::default(),
+                    ..merged
+                });
+            }
+            AppStateAction::Undo => {
+                return match self.history.undo(self.snapshot()) {
+                    Some((previous, history)) => Rc::new(self.with_snapshot(previous, history)),
+                    None => self,
                 }
-                AppStateValue::Checks(checks) => {
-                    update_with_rc!(
-                        self,
-                        { courses, grades, grades_letter, user_courses, user_grades, user_grades_letter, user_checks },
-                        { checks: Rc::new(checks) }
-                    )
+            }
+            AppStateAction::Redo => {
+                return match self.history.redo(self.snapshot()) {
+                    Some((next, history)) => Rc::new(self.with_snapshot(next, history)),
+                    None => self,
                 }
-                AppStateValue::UserCourses(user_courses) => {
-                    update_with_rc!(
-                        self,
-                        { courses, grades, grades_letter, checks, user_grades, user_grades_letter, user_checks },
-                        { user_courses: Rc::new(user_courses) }
-                    )
+            }
+            AppStateAction::Replace(replacement) => {
+                state.entries = replacement.entries;
+                state.next_user_id = replacement.next_user_id;
+            }
+            AppStateAction::AddUserCourse(course) => {
+                state.add_user_course(course);
+            }
+            AppStateAction::RemoveUserCourse(id) => {
+                state.remove_user_course(id);
+            }
+            AppStateAction::DuplicateUserCourse(id) => {
+                state.duplicate_user_course(id);
+            }
+            AppStateAction::MoveUserCourse(id, offset) => {
+                state.move_user_course(id, offset);
+            }
+            AppStateAction::SetGrade(id, grade) => {
+                coalesce = Some((id, EditKind::Grade));
+                if let Some(entry) = state.entry_mut(id) {
+                    entry.grade = grade;
+                    entry.letter = point_to_letter(grade);
                 }
-                AppStateValue::UserGrades(user_grades) => {
-                    update_with_rc!(
-                        self,
-                        { courses, grades, grades_letter, checks, user_courses, user_grades_letter, user_checks },
-                        { user_grades: Rc::new(user_grades) }
-                    )
+            }
+            AppStateAction::SetLetter(id, letter) => {
+                if let Some(entry) = state.entry_mut(id) {
+                    entry.grade = letter_to_point(&letter);
+                    entry.letter = letter;
                 }
-                AppStateValue::UserGradesLetter(user_grades_letter) => {
-                    update_with_rc!(
-                        self,
-                        { courses, grades, grades_letter, checks, user_courses, user_grades, user_checks },
-                        { user_grades_letter: Rc::new(user_grades_letter) }
-                    )
+            }
+            AppStateAction::ToggleIncluded(id) => {
+                if let Some(entry) = state.entry_mut(id) {
+                    entry.included = !entry.included;
                 }
-                AppStateValue::UserChecks(user_checks) => {
-                    update_with_rc!(
-                        self,
-                        { courses, grades, grades_letter, checks, user_courses, user_grades, user_grades_letter },
-                        { user_checks: Rc::new(user_checks) }
-                    )
+            }
+            AppStateAction::SetEnrollment(id, enrollment) => {
+                if let Some(entry) = state.entry_mut(id) {
+                    entry.enrollment = enrollment;
                 }
-            },
+            }
+            AppStateAction::SetName(id, name) => {
+                coalesce = Some((id, EditKind::Name));
+                if let Some(entry) = state.entry_mut(id) {
+                    entry.course.name = name;
+                }
+            }
+            AppStateAction::SetUnit(id, unit) => {
+                coalesce = Some((id, EditKind::Unit));
+                if let Some(entry) = state.entry_mut(id) {
+                    entry.course.unit = unit;
+                }
+            }
+            AppStateAction::SetDetail(id, field, value) => {
+                coalesce = Some((id, EditKind::Detail(field)));
+                if let Some(entry) = state.entry_mut(id) {
+                    let target = match field {
+                        DetailField::Teacher => &mut entry.course.teacher,
+                        DetailField::Description => &mut entry.course.description,
+                        DetailField::Image => &mut entry.course.image,
+                        DetailField::Notes => &mut entry.notes,
+                    };
+                    *target = value;
+                }
+            }
+        }
+        if state == *self {
+            return self;
         }
+        state.history = Rc::new(self.history.record(self.snapshot(), coalesce));
+        Rc::new(state)
     }
 }
 
 pub type AppStateContext = UseReducerHandle<AppState>;
+
+pub const SETTINGS_KEY: &str = "settings";
+
+/// Preferences that are not part of the grade data.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub theme: String,
+    /// Shown on the printable report.
+    pub student_name: String,
+    pub program: String,
+    /// Logs debug messages to the browser console, even in release builds.
+    pub debug_logging: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            theme: "light".to_string(),
+            student_name: String::new(),
+            program: String::new(),
+            debug_logging: false,
+        }
+    }
+}
+
+pub type SettingsContext = UseStateHandle<Settings>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{block_on, MemoryStore};
+    use serde_json::json;
+
+    fn course(id: usize, name: &str) -> Course {
+        Course {
+            id,
+            name: name.to_string(),
+            unit: 3,
+            ..Course::default()
+        }
+    }
+
+    fn storage_with(saved: Value) -> Storage {
+        let storage = Storage::new(MemoryStore::default());
+        block_on(storage.save(StoreName::Snapshots, CURRENT_SNAPSHOT, &saved)).unwrap();
+        storage
+    }
+
+    #[test]
+    fn nothing_saved_loads_nothing() {
+        let storage = Storage::new(MemoryStore::default());
+        assert!(block_on(AppState::load(&storage)).is_none());
+    }
+
+    #[test]
+    fn loads_the_current_version() {
+        let mut entry = CourseEntry::new(EntryId::User(4), course(4, "Thesis"));
+        entry.grade = 88.0;
+        entry.notes = "Draft due in March".to_string();
+        let state = AppState {
+            entries: Rc::new(vec![entry]),
+            next_user_id: 5,
+            ..AppState::default()
+        };
+        let saved = SavedStateRef {
+            version: STATE_VERSION,
+            state: &state,
+        };
+        let storage = storage_with(serde_json::to_value(&saved).unwrap());
+
+        let loaded = block_on(AppState::load(&storage)).unwrap();
+        assert!(loaded == state);
+    }
+
+    #[test]
+    fn migrates_version_1_vectors_into_entries() {
+        let storage = storage_with(json!({
+            "version": 1,
+            "state": {
+                "courses": [course(0, "Algorithms"), course(1, "Databases")],
+                "grades": [91.0, 75.0],
+                "checks": [true, false],
+                "grades_letter": ["A+", "B"],
+                "user_courses": [course(7, "Co-op")],
+                "user_grades": [80.0],
+                "user_checks": [true],
+                "user_grades_letter": ["A-"],
+                "enrollments": {"1": {"term": "2024 Fall", "section": "D100"}},
+            },
+        }));
+
+        let loaded = block_on(AppState::load(&storage)).unwrap();
+        let ids: Vec<EntryId> = loaded.entries.iter().map(|entry| entry.id).collect();
+        assert_eq!(
+            ids,
+            [EntryId::Catalog(0), EntryId::Catalog(1), EntryId::User(0)]
+        );
+        assert_eq!(loaded.next_user_id, 1);
+
+        let databases = loaded.entry(EntryId::Catalog(1)).unwrap();
+        assert_eq!((databases.grade, databases.letter.as_str()), (75.0, "B"));
+        assert!(!databases.included);
+        assert_eq!(databases.enrollment.as_ref().unwrap().section, "D100");
+
+        let co_op = loaded.entry(EntryId::User(0)).unwrap();
+        assert_eq!(co_op.course.id, 0);
+        assert_eq!((co_op.grade, co_op.included), (80.0, true));
+    }
+
+    #[test]
+    fn unknown_versions_are_ignored() {
+        let storage = storage_with(json!({"version": 99, "state": {}}));
+        assert!(block_on(AppState::load(&storage)).is_none());
+    }
+
+    fn user(n: usize, name: &str, grade: f32) -> CourseEntry {
+        let mut entry = CourseEntry::new(EntryId::User(n), course(n, name));
+        entry.grade = grade;
+        entry
+    }
+
+    fn state(entries: Vec<CourseEntry>) -> AppState {
+        let next_user_id = entries
+            .iter()
+            .filter_map(|entry| match entry.id {
+                EntryId::User(n) => Some(n + 1),
+                EntryId::Catalog(_) => None,
+            })
+            .max()
+            .unwrap_or(0);
+        AppState {
+            entries: Rc::new(entries),
+            next_user_id,
+            ..AppState::default()
+        }
+    }
+
+    fn grades(state: &AppState) -> Vec<(EntryId, f32)> {
+        state.entries.iter().map(|e| (e.id, e.grade)).collect()
+    }
+
+    fn ids(state: &AppState) -> Vec<EntryId> {
+        state.entries.iter().map(|e| e.id).collect()
+    }
+
+    /// One catalog course followed by user courses 0, 1 and 2.
+    fn mixed() -> AppState {
+        let catalog = CourseEntry::new(EntryId::Catalog(0), course(0, "Algorithms"));
+        let mut entries = vec![catalog];
+        entries.extend((0..3).map(|n| user(n, "Thesis", 80.0)));
+        state(entries)
+    }
+
+    #[test]
+    fn removing_takes_out_only_user_courses() {
+        let mut state = mixed();
+        assert!(state.remove_user_course(EntryId::Catalog(0)).is_none());
+        let removed = state.remove_user_course(EntryId::User(1)).unwrap();
+        assert_eq!(removed.id, EntryId::User(1));
+        assert_eq!(
+            ids(&state),
+            [EntryId::Catalog(0), EntryId::User(0), EntryId::User(2)]
+        );
+        assert!(state.remove_user_course(EntryId::User(1)).is_none());
+    }
+
+    #[test]
+    fn duplicates_get_a_fresh_id_right_after_the_original() {
+        let mut state = mixed();
+        assert!(state.duplicate_user_course(EntryId::Catalog(0)).is_none());
+
+        let copy = state.duplicate_user_course(EntryId::User(0)).unwrap();
+        assert_eq!(copy, EntryId::User(3));
+        assert_eq!(state.next_user_id, 4);
+        assert_eq!(
+            ids(&state),
+            [
+                EntryId::Catalog(0),
+                EntryId::User(0),
+                EntryId::User(3),
+                EntryId::User(1),
+                EntryId::User(2)
+            ]
+        );
+        let copy = state.entry(copy).unwrap();
+        assert_eq!(copy.course.id, 3);
+        assert_eq!(copy.course.name, "Thesis (copy)");
+        assert_eq!(copy.grade, 80.0);
+    }
+
+    #[test]
+    fn moves_stop_at_either_end_of_the_user_courses() {
+        let mut state = mixed();
+        state.move_user_course(EntryId::User(0), -5);
+        assert_eq!(
+            ids(&state),
+            [
+                EntryId::Catalog(0),
+                EntryId::User(0),
+                EntryId::User(1),
+                EntryId::User(2)
+            ]
+        );
+
+        state.move_user_course(EntryId::User(0), 5);
+        assert_eq!(
+            ids(&state),
+            [
+                EntryId::Catalog(0),
+                EntryId::User(1),
+                EntryId::User(2),
+                EntryId::User(0)
+            ]
+        );
+
+        state.move_user_course(EntryId::User(2), -1);
+        assert_eq!(
+            ids(&state),
+            [
+                EntryId::Catalog(0),
+                EntryId::User(2),
+                EntryId::User(1),
+                EntryId::User(0)
+            ]
+        );
+
+        let before = ids(&state);
+        state.move_user_course(EntryId::Catalog(0), 1);
+        assert_eq!(ids(&state), before);
+    }
+
+    #[test]
+    fn merge_keeps_edits_made_on_either_side() {
+        let base = vec![user(0, "A", 70.0), user(1, "B", 70.0)];
+        let ours = state(vec![user(0, "A", 80.0), user(1, "B", 70.0)]);
+        let theirs = state(vec![user(0, "A", 70.0), user(1, "B", 90.0)]);
+
+        let merged = ours.merged(&base, &theirs);
+        assert_eq!(
+            grades(&merged),
+            [(EntryId::User(0), 80.0), (EntryId::User(1), 90.0)]
+        );
+    }
+
+    #[test]
+    fn merge_prefers_the_incoming_edit_when_both_changed_an_entry() {
+        let base = vec![user(0, "A", 70.0)];
+        let ours = state(vec![user(0, "A", 80.0)]);
+        let theirs = state(vec![user(0, "A", 90.0)]);
+        assert_eq!(
+            grades(&ours.merged(&base, &theirs)),
+            [(EntryId::User(0), 90.0)]
+        );
+    }
+
+    #[test]
+    fn merge_applies_removals_from_either_side() {
+        let base = vec![user(0, "A", 70.0), user(1, "B", 70.0), user(2, "C", 70.0)];
+        let ours = state(vec![user(1, "B", 70.0), user(2, "C", 70.0)]);
+        let theirs = state(vec![user(0, "A", 70.0), user(1, "B", 70.0)]);
+        assert_eq!(
+            grades(&ours.merged(&base, &theirs)),
+            [(EntryId::User(1), 70.0)]
+        );
+    }
+
+    #[test]
+    fn merge_keeps_a_course_edited_here_but_removed_there() {
+        let base = vec![user(0, "A", 70.0)];
+        let ours = state(vec![user(0, "A", 85.0)]);
+        let theirs = state(vec![]);
+        assert_eq!(
+            grades(&ours.merged(&base, &theirs)),
+            [(EntryId::User(0), 85.0)]
+        );
+    }
+
+    #[test]
+    fn merge_renumbers_a_course_both_tabs_added_under_the_same_number() {
+        let base = vec![user(0, "A", 70.0)];
+        let ours = state(vec![user(0, "A", 70.0), user(1, "Ours", 60.0)]);
+        let theirs = state(vec![user(0, "A", 70.0), user(1, "Theirs", 95.0)]);
+
+        let merged = ours.merged(&base, &theirs);
+        let names: Vec<(EntryId, &str)> = merged
+            .entries
+            .iter()
+            .map(|e| (e.id, e.course.name.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                (EntryId::User(0), "A"),
+                (EntryId::User(1), "Theirs"),
+                (EntryId::User(2), "Ours"),
+            ]
+        );
+        assert_eq!(merged.entry(EntryId::User(2)).unwrap().course.id, 2);
+        assert_eq!(merged.next_user_id, 3);
+    }
+
+    #[test]
+    fn merging_a_remote_change_drops_undo_steps() {
+        let start = Rc::new(state(vec![user(0, "A", 70.0)]));
+        let edited = start
+            .clone()
+            .reduce(AppStateAction::SetGrade(EntryId::User(0), 80.0));
+        assert!(edited.can_undo());
+
+        let theirs = state(vec![user(0, "A", 70.0), user(1, "B", 90.0)]);
+        let base = Some(start.entries.clone());
+        let merged = edited.reduce(AppStateAction::Merge(theirs, base));
+        assert_eq!(
+            grades(&merged),
+            [(EntryId::User(0), 80.0), (EntryId::User(1), 90.0)]
+        );
+        assert!(!merged.can_undo());
+
+        // An incoming state this tab already has changes nothing
+        let same = (*merged).clone();
+        let base = Some(merged.entries.clone());
+        let edited_again = merged.reduce(AppStateAction::SetGrade(EntryId::User(1), 91.0));
+        let unchanged = edited_again
+            .clone()
+            .reduce(AppStateAction::Merge(same, base));
+        assert!(Rc::ptr_eq(&unchanged, &edited_again));
+        assert!(unchanged.can_undo());
+    }
+}
@@ -0,0 +1,214 @@
+// src/backup.rs
+use crate::app_state::{same_course, AppState, EntryId, Settings};
+use gpa_core::prereq::course_label;
+use gpa_core::types::Course;
+use gpa_core::validation::{check_grade, check_unit};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// Marks a JSON document as one of our backups.
+const BACKUP_FORMAT: &str = "gpacalculator-backup";
+/// Follows the saved state version, so older backups are read the same way old saves are.
+const BACKUP_VERSION: u32 = 2;
+
+/// Everything the user has entered, together with the catalog it was entered against.
+#[derive(Serialize)]
+pub struct Backup {
+    pub format: String,
+    pub version: u32,
+    /// ISO 8601 time the backup was made.
+    pub created: String,
+    pub settings: Settings,
+    pub state: AppState,
+}
+
+/// A backup as read, before its state is brought up to date.
+#[derive(Deserialize)]
+struct RawBackup {
+    format: String,
+    version: u32,
+    #[serde(default)]
+    created: String,
+    #[serde(default)]
+    settings: Settings,
+    state: Value,
+}
+
+impl Backup {
+    pub fn new(state: &AppState, settings: &Settings) -> Self {
+        Self::created_at(
+            state,
+            settings,
+            String::from(js_sys::Date::new_0().to_iso_string()),
+        )
+    }
+
+    fn created_at(state: &AppState, settings: &Settings, created: String) -> Self {
+        Self {
+            format: BACKUP_FORMAT.to_string(),
+            version: BACKUP_VERSION,
+            created,
+            settings: settings.clone(),
+            state: state.clone(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("backup is always serializable")
+    }
+
+    /// Suggested download name, e.g. `gpa-backup-2024-09-03.json`.
+    pub fn file_name(&self) -> String {
+        format!(
+            "gpa-backup-{}.json",
+            self.created.get(..10).unwrap_or("undated")
+        )
+    }
+
+    /// Parses a backup and checks that it is internally consistent.
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        let raw: RawBackup =
+            serde_json::from_str(text).map_err(|err| format!("not a valid backup: {}", err))?;
+        if raw.format != BACKUP_FORMAT {
+            return Err("this file is not a GPA calculator backup".to_string());
+        }
+        if raw.version > BACKUP_VERSION {
+            return Err(format!(
+                "this backup was made by a newer version of the app (format {})",
+                raw.version
+            ));
+        }
+        let state = AppState::from_saved(raw.version, raw.state)
+            .map_err(|err| format!("not a valid backup: {}", err))?;
+
+        let mut ids = BTreeSet::new();
+        for entry in state.entries.iter() {
+            if !ids.insert(entry.id) {
+                return Err(format!("{:?} appears more than once", entry.id));
+            }
+            if matches!(entry.id, EntryId::User(n) if n >= state.next_user_id) {
+                return Err(format!("{:?} is not below the next user id", entry.id));
+            }
+            // The same rules as typing into the course fields, for the courses that count;
+            // a course just added, or a catalog course left out, may still be unfinished
+            if entry.included {
+                check_grade(entry.grade)
+                    .and(check_unit(entry.course.unit))
+                    .map_err(|err| format!("{}: {}", course_label(&entry.course), err))?;
+            }
+        }
+        Ok(Self {
+            format: raw.format,
+            version: BACKUP_VERSION,
+            created: raw.created,
+            settings: raw.settings,
+            state,
+        })
+    }
+
+    /// The state to restore against the catalog currently loaded, and a description of
+    /// every difference between that catalog and the one the backup was made against.
+    pub fn restore_onto(&self, catalog: &[Course]) -> (AppState, Vec<String>) {
+        if catalog.is_empty() {
+            return (self.state.clone(), vec![]);
+        }
+
+        let mut conflicts = vec![];
+        let saved = &self.state;
+        for entry in saved.catalog_entries() {
+            let old = &entry.course;
+            let has_data = entry.included || entry.grade != 0.0;
+            match catalog.iter().find(|new| same_course(old, new)) {
+                None if has_data => conflicts.push(format!(
+                    "{} is no longer in the catalog; its grade is not restored",
+                    course_label(old)
+                )),
+                Some(new) if new.unit != old.unit => conflicts.push(format!(
+                    "{} now has {} units instead of {}",
+                    course_label(new),
+                    new.unit,
+                    old.unit
+                )),
+                _ => {}
+            }
+        }
+        let added = catalog
+            .iter()
+            .filter(|new| {
+                !saved
+                    .catalog_entries()
+                    .any(|old| same_course(&old.course, new))
+            })
+            .count();
+        if added > 0 {
+            conflicts.push(format!(
+                "{} catalog course(s) were added since the backup and start without a grade",
+                added
+            ));
+        }
+
+        (saved.with_catalog(catalog.to_vec()), conflicts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn backup_with(unit: i32, grade: f32) -> String {
+        json!({
+            "format": BACKUP_FORMAT,
+            "version": BACKUP_VERSION,
+            "state": {
+                "entries": [{
+                    "id": {"User": 0},
+                    "course": {
+                        "id": 0, "code": "CMPT 999", "name": "Thesis", "teacher": "",
+                        "description": "", "image": "", "unit": unit,
+                    },
+                    "grade": grade,
+                    "letter": "A",
+                    "included": true,
+                }],
+                "next_user_id": 1,
+            },
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn accepts_a_valid_backup() {
+        let backup = Backup::from_json(&backup_with(6, 88.0)).unwrap();
+        assert_eq!(backup.state.user_entries().count(), 1);
+    }
+
+    #[test]
+    fn rejects_units_the_course_fields_would_reject() {
+        for unit in [0, -3, 1_000_000_000] {
+            let err = Backup::from_json(&backup_with(unit, 88.0))
+                .err()
+                .expect("backup is rejected");
+            assert!(err.starts_with("CMPT 999: Units"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn restores_a_backup_with_a_course_just_added() {
+        let mut state = AppState::default();
+        state.add_user_course(Course::default());
+        let json = Backup::created_at(&state, &Settings::default(), String::new()).to_json();
+
+        let backup = Backup::from_json(&json).unwrap();
+        assert!(backup.state == state);
+    }
+
+    #[test]
+    fn rejects_grades_outside_0_to_100() {
+        let err = Backup::from_json(&backup_with(3, 120.0))
+            .err()
+            .expect("backup is rejected");
+        assert!(err.starts_with("CMPT 999: Grade"), "{}", err);
+    }
+}
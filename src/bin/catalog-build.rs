@@ -0,0 +1,358 @@
+// src/bin/catalog-build.rs
+//
+// Builds the course catalog from editable sources. Usage:
+//
+//     cargo run --bin catalog-build -- <courses-dir | courses.csv> [-o static/courses/courses.json] [--renumber]
+//
+// A directory is read as one TOML file per course, with the same fields as `Course` except
+// `id`. A CSV file needs a header row naming the columns `code`, `name`, `teacher`,
+// `description`, `image`, `unit`, `prerequisites` and `corequisites` (any order; only
+// `name` and `unit` are required). Requisites use the `CMPT 732 and (CMPT 726 or
+// MATH 240)` notation. Courses already in the output catalog keep their ids and new ones
+// are numbered after them; `--renumber` instead numbers everything from zero in code
+// order. The result is linted before anything is written. Pass `-o -` to print to stdout.
+use gpa_core::catalog::{assign_ids, dropped_courses, lint_courses, Severity};
+use gpa_core::prereq::course_label;
+use gpa_core::types::{Course, Requisite};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const DEFAULT_OUTPUT: &str = "static/courses/courses.json";
+
+fn main() -> ExitCode {
+    let mut source = None;
+    let mut output = PathBuf::from(DEFAULT_OUTPUT);
+    let mut renumber = false;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output" => match args.next() {
+                Some(path) => output = PathBuf::from(path),
+                None => return usage("missing path after -o"),
+            },
+            "--renumber" => renumber = true,
+            _ if source.is_none() => source = Some(PathBuf::from(arg)),
+            _ => return usage(&format!("unexpected argument \"{}\"", arg)),
+        }
+    }
+    let Some(source) = source else {
+        return usage("missing source directory or CSV file");
+    };
+
+    let result = if source.is_dir() {
+        read_toml_dir(&source)
+    } else {
+        read_csv(&source)
+    };
+    let (sources, mut courses): (Vec<String>, Vec<Course>) = match result {
+        Ok(courses) => courses.into_iter().unzip(),
+        Err(errors) => {
+            for error in &errors {
+                eprintln!("error: {}", error);
+            }
+            eprintln!(
+                "{}: {} error(s), nothing written",
+                source.display(),
+                errors.len()
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let existing = if renumber {
+        vec![]
+    } else {
+        // Printing to stdout still keeps the ids of the default catalog
+        let previous = if output.as_os_str() == "-" {
+            Path::new(DEFAULT_OUTPUT)
+        } else {
+            output.as_path()
+        };
+        match read_existing(previous) {
+            Ok(existing) => existing,
+            Err(err) => {
+                eprintln!("error: {}", err);
+                return ExitCode::FAILURE;
+            }
+        }
+    };
+    let dropped = dropped_courses(&courses, &existing);
+    if !dropped.is_empty() {
+        for course in &dropped {
+            eprintln!(
+                "error: {} (id {}) is no longer in the sources",
+                course_label(course),
+                course.id
+            );
+        }
+        eprintln!(
+            "removing courses changes the ids of the courses after them; pass --renumber to do so anyway"
+        );
+        return ExitCode::FAILURE;
+    }
+
+    let order = assign_ids(&mut courses, &existing);
+    let indexed: Vec<(usize, Course)> = courses.iter().cloned().enumerate().collect();
+    let problems = lint_courses(&indexed);
+    for problem in &problems {
+        let source = &sources[order[path_index(&problem.path)]];
+        eprintln!("{} (from {})", problem, source);
+    }
+    if problems.iter().any(|p| p.severity == Severity::Error) {
+        eprintln!("catalog has errors, nothing written");
+        return ExitCode::FAILURE;
+    }
+
+    let mut json = serde_json::to_string_pretty(&courses).expect("courses serialize to JSON");
+    json.push('\n');
+    if output.as_os_str() == "-" {
+        print!("{}", json);
+    } else if let Err(err) = std::fs::write(&output, json) {
+        eprintln!("error: cannot write {}: {}", output.display(), err);
+        return ExitCode::from(2);
+    } else {
+        println!("wrote {} courses to {}", courses.len(), output.display());
+    }
+    ExitCode::SUCCESS
+}
+
+fn usage(message: &str) -> ExitCode {
+    eprintln!("error: {}", message);
+    eprintln!("usage: catalog-build <courses-dir | courses.csv> [-o <output.json>] [--renumber]");
+    ExitCode::from(2)
+}
+
+/// The catalog being replaced, or nothing when it does not exist yet.
+fn read_existing(path: &Path) -> Result<Vec<Course>, String> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(format!("cannot read {}: {}", path.display(), err)),
+    };
+    serde_json::from_str(&text).map_err(|err| {
+        format!(
+            "{}: {}; fix it or pass --renumber to ignore its ids",
+            path.display(),
+            err
+        )
+    })
+}
+
+/// The course index out of a lint path such as `$[3].unit`.
+fn path_index(path: &str) -> usize {
+    path.trim_start_matches("$[")
+        .split(']')
+        .next()
+        .and_then(|index| index.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Courses paired with where they were read from, for error messages.
+type Sourced = Vec<(String, Course)>;
+
+fn read_toml_dir(dir: &Path) -> Result<Sourced, Vec<String>> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|err| vec![format!("cannot read {}: {}", dir.display(), err)])?;
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+
+    let mut courses = vec![];
+    let mut errors = vec![];
+    for path in paths {
+        match read_toml_course(&path) {
+            Ok(course) => courses.push((path.display().to_string(), course)),
+            Err(err) => errors.push(format!("{}: {}", path.display(), err)),
+        }
+    }
+    if errors.is_empty() {
+        Ok(courses)
+    } else {
+        Err(errors)
+    }
+}
+
+fn read_toml_course(path: &Path) -> Result<Course, String> {
+    let text = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+    let mut table: toml::Table = text
+        .parse()
+        .map_err(|err: toml::de::Error| err.to_string())?;
+    if table.contains_key("id") {
+        return Err("`id` is assigned by catalog-build, remove it".to_string());
+    }
+    table.insert("id".to_string(), toml::Value::Integer(0));
+    toml::Value::Table(table)
+        .try_into::<Course>()
+        .map_err(|err| err.to_string())
+}
+
+fn read_csv(path: &Path) -> Result<Sourced, Vec<String>> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|err| vec![format!("cannot read {}: {}", path.display(), err)])?;
+    parse_csv(&path.display().to_string(), &text)
+}
+
+/// The line of `text` a record starts on, counting the header as line 1. The reader
+/// positions a record before any blank lines it skipped, and quoted descriptions can
+/// span lines, so this counts from the first byte of the record itself.
+fn record_line(text: &str, position: &csv::Position) -> usize {
+    let start = position.byte() as usize;
+    let rest = &text[start..];
+    let blank = rest.len() - rest.trim_start_matches(['\r', '\n']).len();
+    text[..start + blank].matches('\n').count() + 1
+}
+
+/// Reads CSV `text`; `source` names it in error messages.
+fn parse_csv(source: &str, text: &str) -> Result<Sourced, Vec<String>> {
+    let mut reader = csv::Reader::from_reader(text.as_bytes());
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|err| vec![format!("{}: {}", source, err)])?
+        .iter()
+        .map(|header| header.trim().to_ascii_lowercase())
+        .collect();
+
+    const KNOWN: [&str; 8] = [
+        "code",
+        "name",
+        "teacher",
+        "description",
+        "image",
+        "unit",
+        "prerequisites",
+        "corequisites",
+    ];
+    let mut errors: Vec<String> = headers
+        .iter()
+        .filter(|header| !KNOWN.contains(&header.as_str()))
+        .map(|header| format!("{}: unknown column \"{}\"", source, header))
+        .collect();
+    for required in ["name", "unit"] {
+        if !headers.iter().any(|header| header == required) {
+            errors.push(format!("{}: missing column \"{}\"", source, required));
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let mut courses = vec![];
+    for record in reader.records() {
+        let record = match record {
+            Ok(record) => record,
+            Err(err) => {
+                let line = err
+                    .position()
+                    .map_or(0, |position| record_line(text, position));
+                errors.push(format!("{}:{}: {}", source, line, err));
+                continue;
+            }
+        };
+        let line = record
+            .position()
+            .map_or(0, |position| record_line(text, position));
+        let location = format!("{}:{}", source, line);
+        let field = |name: &str| {
+            headers
+                .iter()
+                .position(|header| header == name)
+                .and_then(|i| record.get(i))
+                .unwrap_or("")
+                .trim()
+                .to_string()
+        };
+        let requisite = |name: &str| -> Result<Option<Requisite>, String> {
+            let text = field(name);
+            if text.is_empty() {
+                Ok(None)
+            } else {
+                text.parse()
+                    .map(Some)
+                    .map_err(|err| format!("{}: {}: {}", location, name, err))
+            }
+        };
+
+        let unit = match field("unit").parse::<i32>() {
+            Ok(unit) => unit,
+            Err(_) => {
+                errors.push(format!(
+                    "{}: unit \"{}\" is not a whole number",
+                    location,
+                    field("unit")
+                ));
+                continue;
+            }
+        };
+        let (prerequisites, corequisites) =
+            match (requisite("prerequisites"), requisite("corequisites")) {
+                (Ok(prerequisites), Ok(corequisites)) => (prerequisites, corequisites),
+                (Err(err), _) | (_, Err(err)) => {
+                    errors.push(err);
+                    continue;
+                }
+            };
+
+        let course = Course {
+            code: field("code"),
+            name: field("name"),
+            teacher: field("teacher"),
+            description: field("description"),
+            image: field("image"),
+            unit,
+            prerequisites,
+            corequisites,
+            ..Default::default()
+        };
+        courses.push((location, course));
+    }
+
+    if errors.is_empty() {
+        Ok(courses)
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locations(text: &str) -> Vec<String> {
+        parse_csv("courses.csv", text)
+            .unwrap()
+            .into_iter()
+            .map(|(location, _)| location)
+            .collect()
+    }
+
+    #[test]
+    fn multi_line_descriptions_keep_later_rows_on_their_line() {
+        let text = "code,name,unit,description\n\
+                    CMPT 726,Machine Learning,3,\"Supervised learning.\n\nAlso kernels.\"\n\
+                    CMPT 732,Big Data,6,Spark\n";
+        assert_eq!(locations(text), ["courses.csv:2", "courses.csv:5"]);
+        let courses = parse_csv("courses.csv", text).unwrap();
+        assert_eq!(
+            courses[0].1.description,
+            "Supervised learning.\n\nAlso kernels."
+        );
+    }
+
+    #[test]
+    fn blank_lines_are_counted() {
+        let text = "name,unit\nAlgorithms,3\n\n\nDatabases,3\n";
+        assert_eq!(locations(text), ["courses.csv:2", "courses.csv:5"]);
+    }
+
+    #[test]
+    fn errors_name_the_line() {
+        let text = "name,unit\nAlgorithms,3\n\nDatabases,three\n";
+        let errors = parse_csv("courses.csv", text).unwrap_err();
+        assert_eq!(
+            errors,
+            ["courses.csv:4: unit \"three\" is not a whole number"]
+        );
+    }
+}
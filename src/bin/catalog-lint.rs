@@ -0,0 +1,86 @@
+// src/bin/catalog-lint.rs
+//
+// Validates the course catalog. Usage:
+//
+//     cargo run --bin catalog-lint [path/to/courses.json]
+//
+// Prints every problem with its JSON path and exits non-zero if any error was found.
+use gpa_core::catalog::{lint, Problem, Severity};
+use gpa_core::types::Course;
+use serde_json::Value;
+use std::path::Path;
+use std::process::ExitCode;
+
+const DEFAULT_PATH: &str = "static/courses/courses.json";
+
+fn main() -> ExitCode {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_PATH.to_string());
+
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(err) => {
+            eprintln!("error: cannot read {}: {}", path, err);
+            return ExitCode::from(2);
+        }
+    };
+
+    let document = match serde_json::from_str(&text) {
+        Ok(document) => document,
+        Err(err) => {
+            eprintln!(
+                "error: {}:{}:{}: invalid JSON: {}",
+                path,
+                err.line(),
+                err.column(),
+                err
+            );
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut problems = lint(&document);
+    problems.extend(check_local_images(Path::new(&path), &document));
+    for problem in &problems {
+        eprintln!("{}", problem);
+    }
+
+    let errors = problems
+        .iter()
+        .filter(|p| p.severity == Severity::Error)
+        .count();
+    let warnings = problems.len() - errors;
+    if errors > 0 {
+        eprintln!("{}: {} error(s), {} warning(s)", path, errors, warnings);
+        ExitCode::FAILURE
+    } else {
+        println!("{}: ok ({} warning(s))", path, warnings);
+        ExitCode::SUCCESS
+    }
+}
+
+/// Images given as catalog-relative paths must exist next to the catalog.
+fn check_local_images(catalog: &Path, document: &Value) -> Vec<Problem> {
+    let dir = catalog.parent().unwrap_or(Path::new("."));
+    let Some(entries) = document.as_array() else {
+        return vec![];
+    };
+    entries
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entry)| {
+            let course: Course = serde_json::from_value(entry.clone()).ok()?;
+            let image = course.image.trim();
+            let remote = image.contains("://") || image.starts_with("data:");
+            if image.is_empty() || remote || image.starts_with('/') {
+                return None;
+            }
+            (!dir.join(image).is_file()).then(|| Problem {
+                severity: Severity::Error,
+                path: format!("$[{}].image", index),
+                message: format!("{} does not exist", dir.join(image).display()),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,67 @@
+// src/components/catalog_list.rs
+use crate::app_state::{AppStateAction, AppStateContext, CourseEntry};
+use crate::components::field_error::{card_errors, check_input};
+use crate::components::CourseCard;
+use gpa_core::validation::{parse_grade, Field, FieldError};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+#[derive(Properties, Clone, PartialEq)]
+struct CatalogCourseProps {
+    entry: CourseEntry,
+}
+
+/// A catalog course card wired to the app state.
+#[function_component(CatalogCourse)]
+fn catalog_course(props: &CatalogCourseProps) -> Html {
+    let app_state = use_context::<AppStateContext>().expect("No AppStateContext found");
+    let rejected = use_state_eq(Vec::<FieldError>::new);
+    let id = props.entry.id;
+
+    let oninput = {
+        let app_state = app_state.clone();
+        let rejected = rejected.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Some(grade) = check_input(&rejected, Field::Grade, parse_grade(&input.value())) {
+                app_state.dispatch(AppStateAction::SetGrade(id, grade));
+            }
+        })
+    };
+    let onselect = {
+        let app_state = app_state.clone();
+        let rejected = rejected.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            check_input(&rejected, Field::Grade, Ok(()));
+            app_state.dispatch(AppStateAction::SetLetter(id, select.value()));
+        })
+    };
+    let ontoggle = Callback::from(move |_| app_state.dispatch(AppStateAction::ToggleIncluded(id)));
+
+    html! {
+        <CourseCard
+            course={props.entry.course.clone()}
+            grade={props.entry.grade}
+            grade_letter={props.entry.letter.clone()}
+            check={props.entry.included}
+            on_input_change={oninput}
+            on_select_change={onselect}
+            on_toggle={ontoggle}
+            errors={card_errors(&rejected, &props.entry)}
+        />
+    }
+}
+
+/// Cards for every catalog course, in catalog order.
+#[function_component(CatalogList)]
+pub fn catalog_list() -> Html {
+    let app_state = use_context::<AppStateContext>().expect("No AppStateContext found");
+    html! {
+        <span class="course_card_list">
+            { for app_state.catalog_entries().map(|entry| html! {
+                <CatalogCourse key={format!("{:?}", entry.id)} entry={entry.clone()} />
+            }) }
+        </span>
+    }
+}
@@ -1,6 +1,8 @@
 // src/components/course_card.rs
+use crate::components::{CourseImage, FieldErrorMessage};
 use crate::route::Route;
-use crate::types::Course;
+use gpa_core::types::Course;
+use gpa_core::validation::{Field, FieldError};
 use yew::prelude::*;
 use yew_router::prelude::*;
 
@@ -15,6 +17,9 @@ pub struct Props {
     pub on_input_change: Callback<InputEvent>,
     pub on_select_change: Callback<Event>,
     pub on_toggle: Callback<()>,
+    /// Rejected input or invalid values to show under the grade.
+    #[prop_or_default]
+    pub errors: Vec<FieldError>,
 }
 
 impl Component for CourseCard {
@@ -33,13 +38,14 @@ impl Component for CourseCard {
         html! {
             <div class="course_card_container">
                 <Link<Route> to={Route::CourseDetail  { id: ctx.props().course.id }} classes="course_card_anchor">
+                    <div class="course_card_code"> {&ctx.props().course.code}</div>
                     <div class="course_card_name"> {&ctx.props().course.name}</div>
                     <div class="course_card_teacher"> {&ctx.props().course.teacher}</div>
-                    <img class="course_card_image"  src={ctx.props().course.image.clone()}/>
+                    <CourseImage class="course_card_image" course={ctx.props().course.clone()}/>
                 </Link<Route>>
                 <br/>
                 <div class="grade_input">
-                    <input  type="number" value={ctx.props().grade.to_string()} step="any"  {oninput} />
+                    <input  type="number" value={ctx.props().grade.to_string()} step="any"  {oninput} data-app-undo="true" />
                     <select onchange={onselect} value={ctx.props().grade_letter.clone()}>
                         <option value="A+">{"A+"}</option>
                         <option value="A">{"A"}</option>
@@ -55,6 +61,7 @@ impl Component for CourseCard {
                         <input type="checkbox" onclick={ontoggle} checked={ctx.props().check}/>
                     </span>
                 </div>
+                <FieldErrorMessage errors={ctx.props().errors.clone()} field={Field::Grade} />
             </div>
         }
     }
@@ -0,0 +1,97 @@
+// src/components/course_image.rs
+use crate::api::CATALOG_DIR;
+use gpa_core::types::Course;
+use yew::prelude::*;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub course: Course,
+    #[prop_or_default]
+    pub class: Classes,
+}
+
+/// Resolves a catalog `image` to a URL. Absolute URLs and paths are used as they are;
+/// anything else is relative to the directory `courses.json` is served from.
+pub fn resolve_image(image: &str) -> Option<String> {
+    let image = image.trim();
+    if image.is_empty() {
+        None
+    } else if image.starts_with("http://")
+        || image.starts_with("https://")
+        || image.starts_with("data:")
+        || image.starts_with('/')
+    {
+        Some(image.to_string())
+    } else {
+        Some(format!(
+            "{}/{}",
+            CATALOG_DIR,
+            image.trim_start_matches("./")
+        ))
+    }
+}
+
+/// Text shown on the placeholder tile: the course code, or the initials of its name.
+fn placeholder_label(course: &Course) -> String {
+    if !course.code.trim().is_empty() {
+        return course.code.trim().to_string();
+    }
+    let initials: String = course
+        .name
+        .split_whitespace()
+        .filter_map(|word| word.chars().next())
+        .filter(|c| c.is_alphanumeric())
+        .take(3)
+        .collect::<String>()
+        .to_uppercase();
+    if initials.is_empty() {
+        "?".to_string()
+    } else {
+        initials
+    }
+}
+
+/// A stable hue per course so placeholder tiles are told apart at a glance.
+fn placeholder_hue(label: &str) -> u32 {
+    // FNV-1a
+    let hash = label.bytes().fold(0x811c9dc5u32, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x01000193)
+    });
+    hash % 360
+}
+
+#[function_component(CourseImage)]
+pub fn course_image(props: &Props) -> Html {
+    let src = resolve_image(&props.course.image);
+    let failed = use_state(|| false);
+
+    // A new image gets a fresh chance to load.
+    {
+        let failed = failed.clone();
+        use_effect_with(src.clone(), move |_| failed.set(false));
+    }
+
+    match src {
+        Some(src) if !*failed => {
+            let onerror = {
+                let failed = failed.clone();
+                Callback::from(move |_: Event| failed.set(true))
+            };
+            html! {
+                <img class={props.class.clone()} {src} alt={props.course.name.clone()} {onerror}/>
+            }
+        }
+        _ => {
+            let label = placeholder_label(&props.course);
+            let style = format!(
+                "background-color: hsl({}, 55%, 45%);",
+                placeholder_hue(&label)
+            );
+            html! {
+                <div class={classes!("course_image_placeholder", props.class.clone())} {style}>
+                    <span>{label}</span>
+                </div>
+            }
+        }
+    }
+}
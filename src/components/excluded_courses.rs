@@ -0,0 +1,23 @@
+// src/components/excluded_courses.rs
+use crate::hooks::use_gpa_summary;
+use yew::prelude::*;
+
+/// Warns about included courses left out of the GPA because of invalid values.
+#[function_component(ExcludedCourses)]
+pub fn excluded_courses() -> Html {
+    let summary = use_gpa_summary();
+    if summary.excluded.is_empty() {
+        return html! {};
+    }
+    html! {
+        <div class="plan_warnings">
+            <div class="plan_warnings_title">{"Not counted in the GPA until fixed"}</div>
+            <ul>
+                { for summary.excluded.iter().map(|excluded| {
+                    let problems: Vec<String> = excluded.problems.iter().map(ToString::to_string).collect();
+                    html! { <li>{format!("{}: {}", excluded.label, problems.join("; "))}</li> }
+                }) }
+            </ul>
+        </div>
+    }
+}
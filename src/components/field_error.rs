@@ -0,0 +1,52 @@
+// src/components/field_error.rs
+use crate::app_state::CourseEntry;
+use gpa_core::validation::{Field, FieldError};
+use yew::prelude::*;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub errors: Vec<FieldError>,
+    /// Only errors for this field are shown.
+    pub field: Field,
+}
+
+/// Inline message under an input whose value was rejected or is invalid.
+#[function_component(FieldErrorMessage)]
+pub fn field_error_message(props: &Props) -> Html {
+    match props.errors.iter().find(|error| error.field == props.field) {
+        Some(error) => html! { <div class="field_error">{error.to_string()}</div> },
+        None => html! {},
+    }
+}
+
+/// Records or clears the rejection of a typed value, returning the value if it is valid.
+pub fn check_input<T>(
+    rejected: &UseStateHandle<Vec<FieldError>>,
+    field: Field,
+    parsed: Result<T, FieldError>,
+) -> Option<T> {
+    let mut next: Vec<FieldError> = rejected
+        .iter()
+        .filter(|error| error.field != field)
+        .cloned()
+        .collect();
+    let value = parsed.map_err(|error| next.push(error)).ok();
+    if next != **rejected {
+        rejected.set(next);
+    }
+    value
+}
+
+/// Errors to show on a course card: rejected input, then invalid values of an included
+/// course for fields that have no rejected input.
+pub fn card_errors(rejected: &[FieldError], entry: &CourseEntry) -> Vec<FieldError> {
+    let mut errors = rejected.to_vec();
+    if entry.included {
+        for problem in entry.problems() {
+            if !errors.iter().any(|error| error.field == problem.field) {
+                errors.push(problem);
+            }
+        }
+    }
+    errors
+}
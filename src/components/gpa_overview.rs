@@ -1,59 +1,193 @@
 // src/components/gpa_overview.rs
-use crate::app_state::AppStateContext;
-use crate::pages::point_to_pa;
+use crate::app_state::{AppStateContext, EntryId};
+use crate::components::ExcludedCourses;
+use crate::export::{download, to_csv, to_latex, to_markdown};
+use crate::hooks::use_gpa_summary;
 use crate::route::Route;
-use crate::types::Course;
+use crate::share::SharedPlan;
+use crate::summary::Contribution;
+use gpa_core::gpa::GpaTotals;
+use std::collections::BTreeSet;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
 use yew::prelude::*;
 use yew_router::prelude::Link;
 
+/// Column headings shared by the table and the CSV export.
+pub const OVERVIEW_COLUMNS: [&str; 7] = [
+    "Course Name",
+    "Code",
+    "Units",
+    "Percentage",
+    "Letter",
+    "Grade Points",
+    "Quality Points",
+];
+
+/// Columns aligned as numbers in Markdown and LaTeX exports.
+const NUMERIC_COLUMNS: [bool; 7] = [false, false, true, true, false, true, true];
+
+/// A table restricted to the chosen columns and rows, for the Markdown and LaTeX exports.
+struct ExportTable {
+    header: Vec<&'static str>,
+    numeric: Vec<bool>,
+    cells: Vec<Vec<String>>,
+    gpa: String,
+}
+
+fn export_table(
+    rows: &[Contribution],
+    columns: &[bool],
+    excluded: &BTreeSet<EntryId>,
+) -> ExportTable {
+    let chosen = |index: usize| columns.get(index).copied().unwrap_or(false);
+    let rows: Vec<&Contribution> = rows
+        .iter()
+        .filter(|row| !excluded.contains(&row.id))
+        .collect();
+    let gpa = rows
+        .iter()
+        .map(|row| (row.unit, row.grade_points))
+        .collect::<GpaTotals>()
+        .gpa();
+    ExportTable {
+        header: (0..OVERVIEW_COLUMNS.len())
+            .filter(|&i| chosen(i))
+            .map(|i| OVERVIEW_COLUMNS[i])
+            .collect(),
+        numeric: (0..OVERVIEW_COLUMNS.len())
+            .filter(|&i| chosen(i))
+            .map(|i| NUMERIC_COLUMNS[i])
+            .collect(),
+        cells: rows
+            .iter()
+            .map(|row| {
+                row.cells()
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(i, _)| chosen(*i))
+                    .map(|(_, cell)| cell)
+                    .collect()
+            })
+            .collect(),
+        gpa: format!("{:.2}", gpa),
+    }
+}
+
 #[function_component(GPAOverview)]
 pub fn gpa_overview() -> Html {
     let app_state = use_context::<AppStateContext>().expect("No AppStateContext found");
+    let summary = use_gpa_summary();
+    let rows = &summary.courses;
+    let gpa = summary.gpa;
 
-    // Combine fetched and user-added courses
-    let all_courses: Vec<&Course> = app_state
-        .courses
-        .iter()
-        .chain(app_state.user_courses.iter())
-        .collect();
+    let on_export_csv = {
+        let summary = summary.clone();
+        Callback::from(move |_| {
+            let cells: Vec<Vec<String>> = summary.courses.iter().map(Contribution::cells).collect();
+            download(
+                "gpa-courses.csv",
+                "text/csv",
+                &to_csv(&OVERVIEW_COLUMNS, &cells),
+            );
+        })
+    };
 
-    let all_grades: Vec<f32> = app_state
-        .grades
+    // Markdown and LaTeX exports of the chosen columns and courses
+    let columns = use_state(|| vec![true; OVERVIEW_COLUMNS.len()]);
+    // By entry, so the choice stays with the course when rows are added, removed or reordered
+    let excluded = use_state(BTreeSet::<EntryId>::new);
+    let table = export_table(rows, &columns, &excluded);
+    let nothing_to_export = table.header.is_empty() || table.cells.is_empty();
+    let on_export_markdown = {
+        let markdown = format!(
+            "{}\n**Overall GPA: {}**\n",
+            to_markdown(&table.header, &table.numeric, &table.cells),
+            table.gpa
+        );
+        Callback::from(move |_| download("gpa-summary.md", "text/markdown", &markdown))
+    };
+    let on_export_latex = {
+        let latex = to_latex(
+            &table.header,
+            &table.numeric,
+            &table.cells,
+            Some(("Overall GPA", &table.gpa)),
+        );
+        Callback::from(move |_| download("gpa-summary.tex", "application/x-tex", &latex))
+    };
+    let column_options: Html = OVERVIEW_COLUMNS
         .iter()
-        .copied()
-        .chain(app_state.user_grades.iter().copied())
+        .enumerate()
+        .map(|(index, column)| {
+            let onchange = {
+                let columns = columns.clone();
+                Callback::from(move |_| {
+                    let mut next = (*columns).clone();
+                    next[index] = !next[index];
+                    columns.set(next);
+                })
+            };
+            html! {
+                <label>
+                    <input type="checkbox" checked={columns[index]} {onchange} />
+                    {*column}
+                </label>
+            }
+        })
         .collect();
-
-    let all_checks: Vec<bool> = app_state
-        .checks
+    let course_options: Html = rows
         .iter()
-        .copied()
-        .chain(app_state.user_checks.iter().copied())
+        .map(|row| {
+            let id = row.id;
+            let onchange = {
+                let excluded = excluded.clone();
+                Callback::from(move |_| {
+                    let mut next = (*excluded).clone();
+                    if !next.remove(&id) {
+                        next.insert(id);
+                    }
+                    excluded.set(next);
+                })
+            };
+            html! {
+                <label>
+                    <input type="checkbox" checked={!excluded.contains(&id)} {onchange} />
+                    { if row.code.is_empty() { row.name.clone() } else { format!("{} {}", row.code, row.name) } }
+                </label>
+            }
+        })
         .collect();
 
-    // Calculate GPA
-    let mut numer: f32 = 0.0;
-    let mut denomi: f32 = 0.0;
-    for i in 0..all_courses.len() {
-        if all_checks[i] {
-            numer += point_to_pa(all_grades[i]) * all_courses[i].unit as f32;
-            denomi += all_courses[i].unit as f32;
-        }
-    }
-    let gpa = if denomi != 0.0 { numer / denomi } else { 0.0 };
+    // Copy a link to the plan; if the clipboard is unavailable the link is shown instead
+    let share_link = use_state(|| None::<(String, bool)>);
+    let on_share = {
+        let app_state = app_state.clone();
+        let share_link = share_link.clone();
+        Callback::from(move |_| {
+            let url = SharedPlan::from_state(&app_state).url();
+            let share_link = share_link.clone();
+            spawn_local(async move {
+                let copied = match web_sys::window() {
+                    Some(window) => {
+                        let promise = window.navigator().clipboard().write_text(&url);
+                        JsFuture::from(promise).await.is_ok()
+                    }
+                    None => false,
+                };
+                share_link.set(Some((url, copied)));
+            });
+        })
+    };
 
     // Render the courses
-    let courses_view: Html = all_courses
+    let courses_view: Html = rows
         .iter()
-        .enumerate()
-        .filter(|(i, _)| all_checks[*i])
-        .map(|(i, course)| {
-            let grade = all_grades[i];
+        .map(|row| {
             html! {
                 <tr>
-                    <td style="padding: 10px; text-align: center;">{&course.name}</td>
-                    <td style="padding: 10px; text-align: center;">{grade}</td>
-                    <td style="padding: 10px; text-align: center;">{&course.unit}</td>
+                    { for row.cells().into_iter().map(|cell| html! {
+                        <td style="padding: 10px; text-align: center;">{cell}</td>
+                    }) }
                 </tr>
             }
         })
@@ -63,21 +197,64 @@ pub fn gpa_overview() -> Html {
     html! {
         <div>
             <h2 style="text-align: center;">{"GPA Overview"}</h2>
+            <ExcludedCourses />
             <table style="
-                width: 60%; 
-                border-collapse: collapse; 
-                margin: 20px auto; 
+                width: 60%;
+                border-collapse: collapse;
+                margin: 20px auto;
                 border: 1px solid #333;">
                 <tr style="background-color: #f2f2f2;">
-                    <th style="padding: 10px; text-align: center;">{"Course Name"}</th>
-                    <th style="padding: 10px; text-align: center;">{"Course Grade"}</th>
-                    <th style="padding: 10px; text-align: center;">{"Unit"}</th>
+                    { for OVERVIEW_COLUMNS.iter().map(|column| html! {
+                        <th style="padding: 10px; text-align: center;">{*column}</th>
+                    }) }
                 </tr>
                 {courses_view}
             </table>
             <div style="font-size: 18px; font-weight: bold; text-align: center; margin-top: 20px;">
                 {format!("Overall GPA is：{:.2}", gpa)}
             </div>
+            <div style="text-align: center; margin-top: 6px;">
+                {format!(
+                    "{} units counted of {} attempted, {:.2} quality points",
+                    summary.units_included, summary.units_attempted, summary.quality_points
+                )}
+            </div>
+            <div class="export-buttons">
+                <button class="button-28" onclick={on_export_csv} disabled={rows.is_empty()}>
+                    {"Download CSV"}
+                </button>
+                <button class="button-28" onclick={on_share} disabled={rows.is_empty()}>
+                    {"Share"}
+                </button>
+                <Link<Route> to={Route::Report}>
+                    <button class="button-28">{"Printable report"}</button>
+                </Link<Route>>
+            </div>
+            <details class="export-options">
+                <summary>{"Export as Markdown or LaTeX"}</summary>
+                <fieldset>
+                    <legend>{"Columns"}</legend>
+                    {column_options}
+                </fieldset>
+                <fieldset>
+                    <legend>{"Courses"}</legend>
+                    {course_options}
+                </fieldset>
+                <div class="export-buttons">
+                    <button class="button-28" onclick={on_export_markdown} disabled={nothing_to_export}>
+                        {"Download Markdown"}
+                    </button>
+                    <button class="button-28" onclick={on_export_latex} disabled={nothing_to_export}>
+                        {"Download LaTeX"}
+                    </button>
+                </div>
+            </details>
+            if let Some((url, copied)) = (*share_link).clone() {
+                <div class="share_link">
+                    <span>{ if copied { "Link copied to the clipboard:" } else { "Copy this link:" } }</span>
+                    <input type="text" readonly=true value={url} />
+                </div>
+            }
 
             <Link<Route>  to={Route::HomePage } >
                 <div class="gpa-button-container">
@@ -0,0 +1,21 @@
+// src/components/load_error.rs
+use crate::api::ApiError;
+use yew::prelude::*;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub error: ApiError,
+    pub on_retry: Callback<()>,
+}
+
+/// Explains why loading failed and offers to try again.
+#[function_component(LoadError)]
+pub fn load_error(props: &Props) -> Html {
+    let on_retry = props.on_retry.reform(|_| ());
+    html! {
+        <div class="load_error">
+            <p>{props.error.to_string()}</p>
+            <button class="button-28" onclick={on_retry}>{"Try again"}</button>
+        </div>
+    }
+}
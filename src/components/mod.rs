@@ -1,4 +1,20 @@
+mod catalog_list;
 mod course_card;
+mod course_image;
+mod excluded_courses;
+mod field_error;
 mod gpa_overview;
+mod load_error;
+mod navbar;
+mod prereq_graph;
+mod user_course_list;
+pub use catalog_list::CatalogList;
 pub use course_card::CourseCard;
-pub use gpa_overview::GPAOverview;
+pub use course_image::CourseImage;
+pub use excluded_courses::ExcludedCourses;
+pub use field_error::FieldErrorMessage;
+pub use gpa_overview::{GPAOverview, OVERVIEW_COLUMNS};
+pub use load_error::LoadError;
+pub use navbar::Navbar;
+pub use prereq_graph::PrereqGraph;
+pub use user_course_list::UserCourseList;
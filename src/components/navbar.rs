@@ -0,0 +1,62 @@
+// src/components/navbar.rs
+use crate::app_state::{AppStateAction, AppStateContext, SettingsContext};
+use crate::hooks::use_gpa_summary;
+use yew::prelude::*;
+
+/// Switches between the light and dark themes; the body class follows the settings.
+#[function_component(ThemeToggle)]
+pub fn theme_toggle() -> Html {
+    let settings = use_context::<SettingsContext>().expect("No SettingsContext found");
+    let dark = settings.theme == "dark";
+    let onclick = {
+        let settings = settings.clone();
+        Callback::from(move |_| {
+            let mut next = (*settings).clone();
+            next.theme = if dark { "light" } else { "dark" }.to_string();
+            settings.set(next);
+        })
+    };
+    html! {
+        <button id="themeToggleButton" {onclick}>
+            <i id="themeIcon" class={if dark { "fas fa-sun" } else { "fas fa-moon" }}></i>
+        </button>
+    }
+}
+
+#[function_component(HistoryButtons)]
+fn history_buttons() -> Html {
+    let app_state = use_context::<AppStateContext>().expect("No AppStateContext found");
+    let on_undo = {
+        let app_state = app_state.clone();
+        Callback::from(move |_| app_state.dispatch(AppStateAction::Undo))
+    };
+    let on_redo = {
+        let app_state = app_state.clone();
+        Callback::from(move |_| app_state.dispatch(AppStateAction::Redo))
+    };
+    html! {
+        <>
+            <button class="history_button" title="Undo (Ctrl+Z)" onclick={on_undo} disabled={!app_state.can_undo()}>
+                <i class="fas fa-rotate-left"></i>
+            </button>
+            <button class="history_button" title="Redo (Ctrl+Shift+Z)" onclick={on_redo} disabled={!app_state.can_redo()}>
+                <i class="fas fa-rotate-right"></i>
+            </button>
+        </>
+    }
+}
+
+#[function_component(Navbar)]
+pub fn navbar() -> Html {
+    let summary = use_gpa_summary();
+    html! {
+        <div class="navbar">
+            <div class="navbar_title"> {"SFU GPA Calculator written in Rust"}</div>
+            <div class="right-section">
+                <HistoryButtons />
+                <ThemeToggle />
+                <div class="navbar_value"> {"GPA: "}{summary.gpa}</div>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,222 @@
+// src/components/prereq_graph.rs
+use gpa_core::prereq::course_label;
+use gpa_core::types::{Course, Requisite};
+use yew::prelude::*;
+
+const COLUMN_WIDTH: f64 = 150.0;
+const ROW_HEIGHT: f64 = 44.0;
+const NODE_WIDTH: f64 = 110.0;
+const NODE_HEIGHT: f64 = 28.0;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub course: Course,
+    /// Used to expand the requisites of requisites; may be empty.
+    pub catalog: Vec<Course>,
+    /// Codes of courses the student has included in their plan.
+    pub taken: Vec<String>,
+}
+
+enum NodeKind {
+    Target,
+    Course { taken: bool },
+    All,
+    Any,
+}
+
+struct Node {
+    label: String,
+    kind: NodeKind,
+    depth: usize,
+    row: f64,
+}
+
+struct Edge {
+    from: usize,
+    to: usize,
+    corequisite: bool,
+}
+
+#[derive(Default)]
+struct Layout {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+    rows: usize,
+}
+
+impl Layout {
+    /// Lays out a requisite tree leftwards of its parent, one leaf per row. Catalog courses
+    /// are expanded with their own prerequisites unless that would revisit the current path.
+    fn add(
+        &mut self,
+        props: &Props,
+        requisite: &Requisite,
+        depth: usize,
+        path: &mut Vec<String>,
+    ) -> usize {
+        match requisite {
+            Requisite::Course(code) => {
+                let taken = props.taken.iter().any(|t| t.eq_ignore_ascii_case(code));
+                let nested = props
+                    .catalog
+                    .iter()
+                    .find(|c| c.code.eq_ignore_ascii_case(code))
+                    .and_then(|c| c.prerequisites.as_ref())
+                    .filter(|_| !path.iter().any(|p| p.eq_ignore_ascii_case(code)));
+
+                let row = match nested {
+                    Some(nested) => {
+                        path.push(code.clone());
+                        let child = self.add(props, nested, depth + 1, path);
+                        path.pop();
+                        let row = self.nodes[child].row;
+                        self.push_edge_to_next(child, false);
+                        row
+                    }
+                    None => self.next_row(),
+                };
+                self.push(code.clone(), NodeKind::Course { taken }, depth, row)
+            }
+            Requisite::All { all: items } | Requisite::Any { any: items } => {
+                let children: Vec<usize> = items
+                    .iter()
+                    .map(|item| self.add(props, item, depth + 1, path))
+                    .collect();
+                let row = children.iter().map(|&c| self.nodes[c].row).sum::<f64>()
+                    / children.len().max(1) as f64;
+                let (label, kind) = match requisite {
+                    Requisite::All { .. } => ("AND", NodeKind::All),
+                    _ => ("OR", NodeKind::Any),
+                };
+                let id = self.push(label.to_string(), kind, depth, row);
+                for child in children {
+                    self.edges.push(Edge {
+                        from: child,
+                        to: id,
+                        corequisite: false,
+                    });
+                }
+                id
+            }
+        }
+    }
+
+    fn next_row(&mut self) -> f64 {
+        self.rows += 1;
+        (self.rows - 1) as f64
+    }
+
+    fn push(&mut self, label: String, kind: NodeKind, depth: usize, row: f64) -> usize {
+        self.nodes.push(Node {
+            label,
+            kind,
+            depth,
+            row,
+        });
+        self.nodes.len() - 1
+    }
+
+    /// Connects `from` to the node that is about to be pushed.
+    fn push_edge_to_next(&mut self, from: usize, corequisite: bool) {
+        let to = self.nodes.len();
+        self.edges.push(Edge {
+            from,
+            to,
+            corequisite,
+        });
+    }
+}
+
+#[function_component(PrereqGraph)]
+pub fn prereq_graph(props: &Props) -> Html {
+    let course = &props.course;
+    if course.prerequisites.is_none() && course.corequisites.is_none() {
+        return html! {
+            <div class="prereq_graph_empty">{"This course has no prerequisites."}</div>
+        };
+    }
+
+    let mut layout = Layout::default();
+    let mut path = vec![course.code.clone()];
+    let mut children = vec![];
+    if let Some(ref prerequisites) = course.prerequisites {
+        children.push((layout.add(props, prerequisites, 1, &mut path), false));
+    }
+    if let Some(ref corequisites) = course.corequisites {
+        children.push((layout.add(props, corequisites, 1, &mut path), true));
+    }
+    let row = children
+        .iter()
+        .map(|&(c, _)| layout.nodes[c].row)
+        .sum::<f64>()
+        / children.len() as f64;
+    let target = layout.push(course_label(course).to_string(), NodeKind::Target, 0, row);
+    for (child, corequisite) in children {
+        layout.edges.push(Edge {
+            from: child,
+            to: target,
+            corequisite,
+        });
+    }
+
+    let max_depth = layout.nodes.iter().map(|n| n.depth).max().unwrap_or(0);
+    let width = (max_depth + 1) as f64 * COLUMN_WIDTH;
+    let height = layout.rows.max(1) as f64 * ROW_HEIGHT;
+    let center = |node: &Node| {
+        (
+            width - (node.depth as f64 + 0.5) * COLUMN_WIDTH,
+            (node.row + 0.5) * ROW_HEIGHT,
+        )
+    };
+
+    let edges: Html = layout
+        .edges
+        .iter()
+        .map(|edge| {
+            let (x1, y1) = center(&layout.nodes[edge.from]);
+            let (x2, y2) = center(&layout.nodes[edge.to]);
+            html! {
+                <line
+                    class={classes!("prereq_edge", edge.corequisite.then_some("prereq_edge_coreq"))}
+                    x1={(x1 + NODE_WIDTH / 2.0).to_string()} y1={y1.to_string()}
+                    x2={(x2 - NODE_WIDTH / 2.0).to_string()} y2={y2.to_string()}
+                />
+            }
+        })
+        .collect();
+
+    let nodes: Html = layout
+        .nodes
+        .iter()
+        .map(|node| {
+            let (x, y) = center(node);
+            let class = match node.kind {
+                NodeKind::Target => "prereq_node prereq_node_target",
+                NodeKind::Course { taken: true } => "prereq_node prereq_node_taken",
+                NodeKind::Course { taken: false } => "prereq_node",
+                NodeKind::All | NodeKind::Any => "prereq_node prereq_node_gate",
+            };
+            html! {
+                <g class={class}>
+                    <rect
+                        x={(x - NODE_WIDTH / 2.0).to_string()} y={(y - NODE_HEIGHT / 2.0).to_string()}
+                        width={NODE_WIDTH.to_string()} height={NODE_HEIGHT.to_string()} rx="6"
+                    />
+                    <text x={x.to_string()} y={(y + 4.0).to_string()} text-anchor="middle">{&node.label}</text>
+                </g>
+            }
+        })
+        .collect();
+
+    html! {
+        <div class="prereq_graph">
+            <h3>{"Requisites"}</h3>
+            <div>{"Prerequisites: "}{course.prerequisites.as_ref().map(|r| r.to_string()).unwrap_or("none".to_string())}</div>
+            <div>{"Corequisites: "}{course.corequisites.as_ref().map(|r| r.to_string()).unwrap_or("none".to_string())}</div>
+            <svg width={width.to_string()} height={height.to_string()} viewBox={format!("0 0 {} {}", width, height)}>
+                {edges}
+                {nodes}
+            </svg>
+        </div>
+    }
+}
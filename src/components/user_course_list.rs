@@ -0,0 +1,215 @@
+// src/components/user_course_list.rs
+use crate::app_state::{AppStateAction, AppStateContext, CourseEntry, EntryId};
+use crate::components::field_error::{card_errors, check_input};
+use crate::components::{CourseImage, FieldErrorMessage};
+use crate::route::Route;
+use gpa_core::validation::{parse_grade, parse_unit, Field, FieldError};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+#[derive(Properties, Clone, PartialEq)]
+struct UserCourseCardProps {
+    entry: CourseEntry,
+    on_delete: Callback<EntryId>,
+}
+
+/// An editable card for a course the user added.
+#[function_component(UserCourseCard)]
+fn user_course_card(props: &UserCourseCardProps) -> Html {
+    let app_state = use_context::<AppStateContext>().expect("No AppStateContext found");
+    let rejected = use_state_eq(Vec::<FieldError>::new);
+    let entry = &props.entry;
+    let course = &entry.course;
+    let id = entry.id;
+
+    let dispatch = |action: fn(EntryId) -> AppStateAction| {
+        let app_state = app_state.clone();
+        Callback::from(move |_| app_state.dispatch(action(id)))
+    };
+    let on_move_up = dispatch(|id| AppStateAction::MoveUserCourse(id, -1));
+    let on_move_down = dispatch(|id| AppStateAction::MoveUserCourse(id, 1));
+    let on_duplicate = dispatch(AppStateAction::DuplicateUserCourse);
+    let on_toggle = dispatch(AppStateAction::ToggleIncluded);
+    let on_delete = props.on_delete.reform(move |_| id);
+
+    let on_name_input = {
+        let app_state = app_state.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            app_state.dispatch(AppStateAction::SetName(id, input.value()));
+        })
+    };
+    let on_unit_input = {
+        let app_state = app_state.clone();
+        let rejected = rejected.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Some(unit) = check_input(&rejected, Field::Unit, parse_unit(&input.value())) {
+                app_state.dispatch(AppStateAction::SetUnit(id, unit));
+            }
+        })
+    };
+    let on_grade_input = {
+        let app_state = app_state.clone();
+        let rejected = rejected.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Some(grade) = check_input(&rejected, Field::Grade, parse_grade(&input.value())) {
+                app_state.dispatch(AppStateAction::SetGrade(id, grade));
+            }
+        })
+    };
+    let on_grade_letter_change = {
+        let app_state = app_state.clone();
+        let rejected = rejected.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            check_input(&rejected, Field::Grade, Ok(()));
+            app_state.dispatch(AppStateAction::SetLetter(id, select.value()));
+        })
+    };
+    let errors = card_errors(&rejected, entry);
+
+    html! {
+        <div class="course_card_container">
+            <div class="user_course_actions">
+                <button title="Move up" onclick={on_move_up}>
+                    <i class="fas fa-arrow-up"></i>
+                </button>
+                <button title="Move down" onclick={on_move_down}>
+                    <i class="fas fa-arrow-down"></i>
+                </button>
+                <button title="Duplicate" onclick={on_duplicate}>
+                    <i class="fas fa-copy"></i>
+                </button>
+                <Link<Route> to={Route::UserCourseDetail { id: course.id }}>
+                    <button title="Details"><i class="fas fa-pen-to-square"></i></button>
+                </Link<Route>>
+                <button title="Delete" onclick={on_delete}>
+                    <i class="fas fa-trash"></i>
+                </button>
+            </div>
+            <CourseImage class="user_course_image" course={course.clone()}/>
+            <div class="form-group">
+                <label for="cname">{ "Course Name: " }</label>
+                <input
+                    type="text"
+                    id="cname"
+                    placeholder="Course Name"
+                    value={course.name.clone()}
+                    oninput={on_name_input}
+                    data-app-undo="true"
+                />
+                <FieldErrorMessage errors={errors.clone()} field={Field::Name} />
+                <br/>
+            </div>
+
+            <div class="form-group">
+                <label for="unit">{ "Unit: " }</label>
+                <input
+                    type="text"
+                    id="unit"
+                    placeholder="Unit"
+                    value={course.unit.to_string()}
+                    oninput={on_unit_input}
+                    data-app-undo="true"
+                />
+                <FieldErrorMessage errors={errors.clone()} field={Field::Unit} />
+                <br/>
+            </div>
+
+            <div class="form-group">
+                <label for="grade">{ "Grade: " }</label>
+                <input
+                    type="text"
+                    id="grade"
+                    placeholder="Grade"
+                    value={entry.grade.to_string()}
+                    oninput={on_grade_input}
+                    data-app-undo="true"
+                />
+                <select
+                    id="grade_letter"
+                    value={entry.letter.clone()}
+                    onchange={on_grade_letter_change}
+                >
+                    <option value="A+">{"A+"}</option>
+                    <option value="A">{"A"}</option>
+                    <option value="A-">{"A-"}</option>
+                    <option value="B+">{"B+"}</option>
+                    <option value="B">{"B"}</option>
+                    <option value="B-">{"B-"}</option>
+                    <option value="C+">{"C+"}</option>
+                    <option value="C">{"C"}</option>
+                    <option value="F">{"F"}</option>
+                </select>
+                <FieldErrorMessage errors={errors} field={Field::Grade} />
+            </div>
+            <input
+                type="checkbox"
+                checked={entry.included}
+                onclick={on_toggle}
+            />
+        </div>
+    }
+}
+
+/// Cards for the user's own courses, with an undo offer after one is deleted.
+#[function_component(UserCourseList)]
+pub fn user_course_list() -> Html {
+    let app_state = use_context::<AppStateContext>().expect("No AppStateContext found");
+    // The course just deleted and its name, offered for undo until the next edit
+    let deleted = use_state_eq(|| None::<(EntryId, String)>);
+    let deleting = use_mut_ref(|| false);
+
+    // Any change other than the deletion itself withdraws the offer
+    {
+        let deleted = deleted.clone();
+        let deleting = deleting.clone();
+        use_effect_with(app_state.entries.clone(), move |_| {
+            if !deleting.replace(false) {
+                deleted.set(None);
+            }
+        });
+    }
+
+    let on_delete = {
+        let app_state = app_state.clone();
+        let deleted = deleted.clone();
+        Callback::from(move |id: EntryId| {
+            if let Some(entry) = app_state.entry(id) {
+                deleted.set(Some((id, entry.course.name.clone())));
+                *deleting.borrow_mut() = true;
+                app_state.dispatch(AppStateAction::RemoveUserCourse(id));
+            }
+        })
+    };
+    let on_undo = {
+        let app_state = app_state.clone();
+        Callback::from(move |_| app_state.dispatch(AppStateAction::Undo))
+    };
+
+    html! {
+        <>
+            // Hidden again once the course is back, e.g. after Ctrl+Z
+            if let Some((_, name)) = deleted.as_ref().filter(|(id, _)| app_state.entry(*id).is_none()) {
+                <div class="undo_notice">
+                    <span>
+                        { if name.is_empty() { "Deleted a course.".to_string() } else { format!("Deleted {}.", name) } }
+                    </span>
+                    <button class="button-28" onclick={on_undo}>{"Undo"}</button>
+                </div>
+            }
+            <div class="course_card_list">
+                { for app_state.user_entries().map(|entry| html! {
+                    <UserCourseCard
+                        key={format!("{:?}", entry.id)}
+                        entry={entry.clone()}
+                        on_delete={on_delete.clone()}
+                    />
+                }) }
+            </div>
+        </>
+    }
+}
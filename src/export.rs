@@ -0,0 +1,250 @@
+// src/export.rs
+use gloo::file::{Blob, ObjectUrl};
+use gloo::timers::callback::Timeout;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlAnchorElement;
+
+/// Quotes a CSV field when it contains a delimiter, quote or line break. Text that a
+/// spreadsheet would run as a formula gets a leading `'` so it is shown as typed.
+fn csv_field(field: &str) -> String {
+    let formula =
+        field.starts_with(['=', '+', '-', '@', '\t', '\r']) && field.parse::<f64>().is_err();
+    let field = if formula {
+        format!("'{}", field)
+    } else {
+        field.to_string()
+    };
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
+
+/// Builds a CSV document (RFC 4180, CRLF line endings) from a header and rows.
+pub fn to_csv<R: AsRef<[String]>>(header: &[&str], rows: &[R]) -> String {
+    let mut out = String::new();
+    let header: Vec<String> = header.iter().map(|h| csv_field(h)).collect();
+    out.push_str(&header.join(","));
+    out.push_str("\r\n");
+    for row in rows {
+        let fields: Vec<String> = row.as_ref().iter().map(|f| csv_field(f)).collect();
+        out.push_str(&fields.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+fn markdown_cell(cell: &str) -> String {
+    cell.replace('|', "\\|").replace(['\n', '\r'], " ")
+}
+
+/// Builds a GitHub-flavored Markdown table; `numeric` columns are right-aligned.
+pub fn to_markdown<R: AsRef<[String]>>(header: &[&str], numeric: &[bool], rows: &[R]) -> String {
+    let mut out = String::new();
+    let header: Vec<String> = header.iter().map(|h| markdown_cell(h)).collect();
+    out.push_str(&format!("| {} |\n", header.join(" | ")));
+    let rules: Vec<&str> = numeric
+        .iter()
+        .map(|&numeric| if numeric { "---:" } else { "---" })
+        .collect();
+    out.push_str(&format!("| {} |\n", rules.join(" | ")));
+    for row in rows {
+        let cells: Vec<String> = row.as_ref().iter().map(|c| markdown_cell(c)).collect();
+        out.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+    out
+}
+
+fn latex_cell(cell: &str) -> String {
+    let mut out = String::new();
+    for c in cell.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '~' => out.push_str("\\textasciitilde{}"),
+            '^' => out.push_str("\\textasciicircum{}"),
+            '\\' => out.push_str("\\textbackslash{}"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Builds a LaTeX `tabular` environment; `numeric` columns are right-aligned. `footer`
+/// is an optional last row given as a label and a value, e.g. the overall GPA.
+pub fn to_latex<R: AsRef<[String]>>(
+    header: &[&str],
+    numeric: &[bool],
+    rows: &[R],
+    footer: Option<(&str, &str)>,
+) -> String {
+    let spec: String = numeric
+        .iter()
+        .map(|&numeric| if numeric { 'r' } else { 'l' })
+        .collect();
+    let line = |cells: Vec<String>| format!("  {} \\\\\n", cells.join(" & "));
+    let mut out = format!("\\begin{{tabular}}{{{}}}\n  \\hline\n", spec);
+    out.push_str(&line(header.iter().map(|h| latex_cell(h)).collect()));
+    out.push_str("  \\hline\n");
+    for row in rows {
+        out.push_str(&line(row.as_ref().iter().map(|c| latex_cell(c)).collect()));
+    }
+    out.push_str("  \\hline\n");
+    if let Some((label, value)) = footer {
+        if header.len() > 1 {
+            out.push_str(&line(vec![
+                format!(
+                    "\\multicolumn{{{}}}{{l}}{{{}}}",
+                    header.len() - 1,
+                    latex_cell(label)
+                ),
+                latex_cell(value),
+            ]));
+        } else {
+            out.push_str(&line(vec![format!(
+                "{} {}",
+                latex_cell(label),
+                latex_cell(value)
+            )]));
+        }
+        out.push_str("  \\hline\n");
+    }
+    out.push_str("\\end{tabular}\n");
+    out
+}
+
+/// How long the object URL outlives the click. Firefox and Safari start the download
+/// asynchronously and fail if the URL has already been revoked.
+const REVOKE_DELAY_MS: u32 = 60_000;
+
+/// Offers `contents` to the user as a downloaded file.
+pub fn download(filename: &str, mime_type: &str, contents: &str) {
+    let blob = Blob::new_with_options(contents, Some(mime_type));
+    let url = ObjectUrl::from(blob);
+    let document = gloo::utils::document();
+    let anchor: HtmlAnchorElement = document
+        .create_element("a")
+        .expect("can create an anchor element")
+        .unchecked_into();
+    anchor.set_href(&url);
+    anchor.set_download(filename);
+    // Firefox ignores clicks on anchors that are not in the document
+    let body = gloo::utils::body();
+    let _ = body.append_child(&anchor);
+    anchor.click();
+    anchor.remove();
+    // Dropping the URL revokes it
+    Timeout::new(REVOKE_DELAY_MS, move || drop(url)).forget();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn csv_leaves_plain_fields_alone() {
+        let csv = to_csv(&["Course", "Grade"], &rows(&[&["CMPT 120", "91"]]));
+        assert_eq!(csv, "Course,Grade\r\nCMPT 120,91\r\n");
+    }
+
+    #[test]
+    fn csv_quotes_commas_quotes_and_line_breaks() {
+        let csv = to_csv(
+            &["Name, full", "Notes"],
+            &rows(&[
+                &["Data \"Science\"", "line one\nline two"],
+                &["Intro", "ends with\r"],
+            ]),
+        );
+        assert_eq!(
+            csv,
+            "\"Name, full\",Notes\r\n\
+             \"Data \"\"Science\"\"\",\"line one\nline two\"\r\n\
+             Intro,\"ends with\r\"\r\n"
+        );
+    }
+
+    #[test]
+    fn csv_neutralizes_formulas_but_not_negative_numbers() {
+        let csv = to_csv(
+            &["Course", "Grade"],
+            &rows(&[
+                &["=HYPERLINK(\"x\")", "-1.5"],
+                &["+1 course", "@SUM(A1)"],
+                &["-Intro", "91"],
+            ]),
+        );
+        assert_eq!(
+            csv,
+            "Course,Grade\r\n\
+             \"'=HYPERLINK(\"\"x\"\")\",-1.5\r\n\
+             '+1 course,'@SUM(A1)\r\n\
+             '-Intro,91\r\n"
+        );
+    }
+
+    #[test]
+    fn csv_of_no_rows_is_just_the_header() {
+        let csv = to_csv::<Vec<String>>(&["Course"], &[]);
+        assert_eq!(csv, "Course\r\n");
+    }
+
+    #[test]
+    fn markdown_escapes_pipes_and_flattens_line_breaks() {
+        let markdown = to_markdown(
+            &["Course | Title", "Units"],
+            &[false, true],
+            &rows(&[&["A|B", "3"], &["two\nlines", "4"]]),
+        );
+        assert_eq!(
+            markdown,
+            "| Course \\| Title | Units |\n\
+             | --- | ---: |\n\
+             | A\\|B | 3 |\n\
+             | two lines | 4 |\n"
+        );
+    }
+
+    #[test]
+    fn latex_escapes_special_characters() {
+        let latex = to_latex(
+            &["Course"],
+            &[false],
+            &rows(&[&[r"R&D 100% $5 #1 a_b {x} ~ ^ \"]]),
+            None,
+        );
+        let escaped =
+            r"R\&D 100\% \$5 \#1 a\_b \{x\} \textasciitilde{} \textasciicircum{} \textbackslash{}";
+        assert!(latex.contains(escaped), "{}", latex);
+    }
+
+    #[test]
+    fn latex_footer_spans_all_but_the_last_column() {
+        let latex = to_latex(
+            &["Course", "Grade Points"],
+            &[false, true],
+            &rows(&[&["CMPT 120", "4.33"]]),
+            Some(("Overall GPA", "4.33")),
+        );
+        let expected = r"\begin{tabular}{lr}
+  \hline
+  Course & Grade Points \\
+  \hline
+  CMPT 120 & 4.33 \\
+  \hline
+  \multicolumn{1}{l}{Overall GPA} & 4.33 \\
+  \hline
+\end{tabular}
+";
+        assert_eq!(latex, expected);
+    }
+}
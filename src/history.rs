@@ -0,0 +1,198 @@
+// src/history.rs
+
+/// How many steps can be undone.
+const HISTORY_LIMIT: usize = 100;
+
+/// Bounded undo/redo stacks of snapshots. Consecutive edits with the same key (e.g. the
+/// keystrokes typing one grade) are coalesced into a single step.
+#[derive(Clone)]
+pub struct History<T, K> {
+    past: Vec<T>,
+    future: Vec<T>,
+    last_key: Option<K>,
+}
+
+impl<T, K> Default for History<T, K> {
+    fn default() -> Self {
+        Self {
+            past: vec![],
+            future: vec![],
+            last_key: None,
+        }
+    }
+}
+
+impl<T: Clone, K: PartialEq + Clone> History<T, K> {
+    /// Records `before` as the state preceding an edit. Any redo steps are dropped.
+    pub fn record(&self, before: T, key: Option<K>) -> Self {
+        let coalesced = key.is_some() && key == self.last_key;
+        let mut past = self.past.clone();
+        if !coalesced {
+            past.push(before);
+            if past.len() > HISTORY_LIMIT {
+                past.remove(0);
+            }
+        }
+        Self {
+            past,
+            future: vec![],
+            last_key: key,
+        }
+    }
+
+    /// The snapshot to go back to from `current`, and the history after doing so.
+    pub fn undo(&self, current: T) -> Option<(T, Self)> {
+        let mut past = self.past.clone();
+        let previous = past.pop()?;
+        let mut future = self.future.clone();
+        future.push(current);
+        Some((
+            previous,
+            Self {
+                past,
+                future,
+                last_key: None,
+            },
+        ))
+    }
+
+    /// The snapshot to go forward to from `current`, and the history after doing so.
+    pub fn redo(&self, current: T) -> Option<(T, Self)> {
+        let mut future = self.future.clone();
+        let next = future.pop()?;
+        let mut past = self.past.clone();
+        past.push(current);
+        Some((
+            next,
+            Self {
+                past,
+                future,
+                last_key: None,
+            },
+        ))
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.past.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.future.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Applies edits to an integer, recording each like the reducer does.
+    struct Editor {
+        value: i32,
+        history: History<i32, &'static str>,
+    }
+
+    impl Editor {
+        fn new() -> Self {
+            Self {
+                value: 0,
+                history: History::default(),
+            }
+        }
+
+        fn edit(&mut self, value: i32, key: Option<&'static str>) {
+            self.history = self.history.record(self.value, key);
+            self.value = value;
+        }
+
+        fn undo(&mut self) -> bool {
+            match self.history.undo(self.value) {
+                Some((value, history)) => {
+                    (self.value, self.history) = (value, history);
+                    true
+                }
+                None => false,
+            }
+        }
+
+        fn redo(&mut self) -> bool {
+            match self.history.redo(self.value) {
+                Some((value, history)) => {
+                    (self.value, self.history) = (value, history);
+                    true
+                }
+                None => false,
+            }
+        }
+    }
+
+    #[test]
+    fn edits_with_the_same_key_undo_together() {
+        let mut editor = Editor::new();
+        editor.edit(8, Some("grade"));
+        editor.edit(85, Some("grade"));
+        editor.edit(3, Some("unit"));
+
+        assert!(editor.undo());
+        assert_eq!(editor.value, 85);
+        assert!(editor.undo());
+        assert_eq!(editor.value, 0);
+        assert!(!editor.history.can_undo());
+    }
+
+    #[test]
+    fn edits_without_a_key_are_never_coalesced() {
+        let mut editor = Editor::new();
+        editor.edit(1, None);
+        editor.edit(2, None);
+        assert!(editor.undo());
+        assert_eq!(editor.value, 1);
+    }
+
+    #[test]
+    fn undo_ends_coalescing() {
+        let mut editor = Editor::new();
+        editor.edit(1, Some("grade"));
+        editor.undo();
+        editor.edit(2, Some("grade"));
+        assert!(editor.undo());
+        assert_eq!(editor.value, 0);
+    }
+
+    #[test]
+    fn redo_steps_are_dropped_by_a_new_edit() {
+        let mut editor = Editor::new();
+        editor.edit(1, None);
+        editor.edit(2, None);
+        editor.undo();
+        assert!(editor.history.can_redo());
+
+        editor.edit(5, None);
+        assert!(!editor.history.can_redo());
+        assert!(!editor.redo());
+        assert_eq!(editor.value, 5);
+    }
+
+    #[test]
+    fn undo_then_redo_returns_to_the_edit() {
+        let mut editor = Editor::new();
+        editor.edit(1, None);
+        editor.undo();
+        assert!(editor.redo());
+        assert_eq!(editor.value, 1);
+        assert!(!editor.redo());
+    }
+
+    #[test]
+    fn only_the_last_steps_are_kept() {
+        let mut editor = Editor::new();
+        for value in 1..=HISTORY_LIMIT as i32 + 5 {
+            editor.edit(value, None);
+        }
+        let mut undone = 0;
+        while editor.undo() {
+            undone += 1;
+        }
+        assert_eq!(undone, HISTORY_LIMIT);
+        assert_eq!(editor.value, 5);
+    }
+}
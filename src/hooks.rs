@@ -0,0 +1,53 @@
+// src/hooks.rs
+use crate::api::{get_courses, ApiError};
+use crate::app_state::{AppStateAction, AppStateContext};
+use crate::summary::GpaSummary;
+use gpa_core::types::Course;
+use std::rc::Rc;
+use yew::prelude::*;
+
+#[derive(Clone, PartialEq)]
+pub enum CatalogStatus {
+    Loading,
+    Loaded,
+    Failed(ApiError),
+}
+
+/// Fetches the catalog into the app state when the component mounts. Returns how that
+/// went and a callback that fetches it again.
+#[hook]
+pub fn use_catalog() -> (CatalogStatus, Callback<()>) {
+    let app_state = use_context::<AppStateContext>().expect("No AppStateContext found");
+    let status = use_state(|| CatalogStatus::Loading);
+
+    let load = {
+        let status = status.clone();
+        Callback::from(move |_| {
+            status.set(CatalogStatus::Loading);
+            let status = status.clone();
+            let app_state = app_state.clone();
+            get_courses(Callback::from(
+                move |result: Result<Vec<Course>, ApiError>| match result {
+                    Ok(courses) => {
+                        status.set(CatalogStatus::Loaded);
+                        app_state.dispatch(AppStateAction::LoadCatalog(courses));
+                    }
+                    Err(error) => status.set(CatalogStatus::Failed(error)),
+                },
+            ));
+        })
+    };
+    {
+        let load = load.clone();
+        use_effect_with((), move |_| load.emit(()));
+    }
+
+    ((*status).clone(), load)
+}
+
+/// The summary of the current state, recomputed only when the grades change.
+#[hook]
+pub fn use_gpa_summary() -> Rc<GpaSummary> {
+    let app_state = use_context::<AppStateContext>().expect("No AppStateContext found");
+    use_memo((*app_state).clone(), GpaSummary::from_state)
+}
@@ -0,0 +1,354 @@
+// src/import.rs
+use crate::app_state::{AppState, EntryId};
+use gpa_core::grading::{letter_to_point, point_to_letter, LETTER_GRADES};
+use gpa_core::types::{Course, Enrollment, Term};
+use gpa_core::validation::{parse_grade, parse_unit};
+
+/// What a CSV column holds.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ImportField {
+    Ignore,
+    Name,
+    Code,
+    Units,
+    Percentage,
+    Letter,
+}
+
+impl ImportField {
+    pub const ALL: [ImportField; 6] = [
+        ImportField::Ignore,
+        ImportField::Name,
+        ImportField::Code,
+        ImportField::Units,
+        ImportField::Percentage,
+        ImportField::Letter,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ImportField::Ignore => "(ignore)",
+            ImportField::Name => "Course name",
+            ImportField::Code => "Course code",
+            ImportField::Units => "Units",
+            ImportField::Percentage => "Percentage",
+            ImportField::Letter => "Letter grade",
+        }
+    }
+}
+
+/// A data row of a CSV file.
+#[derive(Clone, PartialEq, Debug)]
+pub struct CsvRow {
+    /// Line the row starts on, counting the header as line 1.
+    pub line: usize,
+    pub fields: Vec<String>,
+}
+
+/// Guesses what each column holds from its header, and for grade columns from the first
+/// value; anything unrecognized is ignored.
+pub fn guess_mapping(headers: &[String], rows: &[CsvRow]) -> Vec<ImportField> {
+    let mut mapping: Vec<ImportField> = headers
+        .iter()
+        .map(|header| {
+            let header = header.to_ascii_lowercase();
+            if header.contains("code") || header == "course" {
+                ImportField::Code
+            } else if header.contains("name") || header.contains("title") {
+                ImportField::Name
+            } else if header.contains("unit") || header.contains("credit") {
+                ImportField::Units
+            } else if header.contains("letter") {
+                ImportField::Letter
+            } else if header.contains('%')
+                || header.contains("percent")
+                || header.contains("score")
+                || header.contains("mark")
+                || header.contains("grade")
+            {
+                ImportField::Percentage
+            } else {
+                ImportField::Ignore
+            }
+        })
+        .collect();
+
+    // A "Grade" column of letters holds letter grades, not percentages.
+    for (column, field) in mapping.iter_mut().enumerate() {
+        let first_value = rows
+            .iter()
+            .filter_map(|row| row.fields.get(column))
+            .find(|value| !value.is_empty());
+        if *field == ImportField::Percentage
+            && first_value.is_some_and(|v| v.trim_end_matches('%').parse::<f32>().is_err())
+        {
+            *field = ImportField::Letter;
+        }
+    }
+
+    // Only the first column claiming a field keeps it.
+    for i in 0..mapping.len() {
+        if mapping[i] != ImportField::Ignore && mapping[..i].contains(&mapping[i]) {
+            mapping[i] = ImportField::Ignore;
+        }
+    }
+    mapping
+}
+
+/// The line a record starts on. Quoted fields can span lines, and the reader positions a
+/// record where it started looking, before any blank lines it skipped.
+fn record_line(text: &str, position: &csv::Position) -> usize {
+    let start = position.byte() as usize;
+    let rest = &text[start..];
+    let blank = rest.len() - rest.trim_start_matches(['\r', '\n']).len();
+    text[..start + blank].matches('\n').count() + 1
+}
+
+/// Splits CSV text into a header row and data rows. Blank lines are skipped.
+pub fn parse_csv(text: &str) -> Result<(Vec<String>, Vec<CsvRow>), String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(text.as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|err| err.to_string())?
+        .iter()
+        .map(str::to_string)
+        .collect();
+    let mut rows = vec![];
+    for record in reader.records() {
+        let record = record.map_err(|err| err.to_string())?;
+        if record.iter().all(str::is_empty) {
+            continue;
+        }
+        let line = record
+            .position()
+            .map_or(0, |position| record_line(text, position));
+        rows.push(CsvRow {
+            line,
+            fields: record.iter().map(str::to_string).collect(),
+        });
+    }
+    Ok((headers, rows))
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum ImportTarget {
+    /// Catalog id of the matched course.
+    Catalog(usize),
+    NewCourse,
+}
+
+/// One data row, interpreted through the column mapping.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ImportRow {
+    /// Line in the file, counting the header as line 1.
+    pub line: usize,
+    pub name: String,
+    pub code: String,
+    pub unit: i32,
+    pub grade: f32,
+    pub letter: String,
+    /// Term the course was taken in, when the source says.
+    pub term: Option<Term>,
+    pub target: ImportTarget,
+    pub errors: Vec<String>,
+}
+
+impl ImportRow {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+fn normalize_code(code: &str) -> String {
+    code.split_whitespace()
+        .collect::<String>()
+        .to_ascii_uppercase()
+}
+
+pub(crate) fn find_in_catalog(catalog: &[Course], code: &str, name: &str) -> Option<usize> {
+    if !code.is_empty() {
+        let code = normalize_code(code);
+        if let Some(index) = catalog
+            .iter()
+            .position(|c| !c.code.is_empty() && normalize_code(&c.code) == code)
+        {
+            return Some(index);
+        }
+    }
+    if !name.is_empty() {
+        return catalog
+            .iter()
+            .position(|c| c.name.trim().eq_ignore_ascii_case(name));
+    }
+    None
+}
+
+/// Interprets every row and matches it against the catalog by code, then by name.
+pub fn plan_import(rows: &[CsvRow], mapping: &[ImportField], catalog: &[Course]) -> Vec<ImportRow> {
+    rows.iter()
+        .map(|row| {
+            let field = |wanted: ImportField| {
+                mapping
+                    .iter()
+                    .position(|&f| f == wanted)
+                    .and_then(|column| row.fields.get(column))
+                    .map(|value| value.trim().to_string())
+                    .unwrap_or_default()
+            };
+            let name = field(ImportField::Name);
+            let code = field(ImportField::Code);
+            let units = field(ImportField::Units);
+            let percentage = field(ImportField::Percentage);
+            let letter = field(ImportField::Letter).to_ascii_uppercase();
+            let mut errors = vec![];
+
+            let matched = find_in_catalog(catalog, &code, &name).map(|index| &catalog[index]);
+            let target = match matched {
+                Some(course) => ImportTarget::Catalog(course.id),
+                None => ImportTarget::NewCourse,
+            };
+
+            let unit = match (parse_unit(&units), matched) {
+                (Ok(unit), _) => unit,
+                (_, Some(course)) if units.is_empty() => course.unit,
+                _ if units.is_empty() => {
+                    errors.push("units are required for courses not in the catalog".to_string());
+                    0
+                }
+                (Err(err), _) => {
+                    errors.push(format!("{} (got \"{}\")", err, units));
+                    0
+                }
+            };
+
+            let letter_valid = LETTER_GRADES.contains(&letter.as_str());
+            if !letter.is_empty() && !letter_valid {
+                errors.push(format!("unknown letter grade \"{}\"", letter));
+            }
+            let (grade, letter) = if !percentage.is_empty() {
+                match parse_grade(percentage.trim_end_matches('%')) {
+                    Ok(grade) => {
+                        let letter = if letter_valid {
+                            letter
+                        } else {
+                            point_to_letter(grade)
+                        };
+                        (grade, letter)
+                    }
+                    Err(err) => {
+                        errors.push(format!("{} (got \"{}\")", err, percentage));
+                        (0.0, letter)
+                    }
+                }
+            } else if letter_valid {
+                (letter_to_point(&letter), letter)
+            } else {
+                if letter.is_empty() {
+                    errors.push("no percentage or letter grade".to_string());
+                }
+                (0.0, letter)
+            };
+
+            if target == ImportTarget::NewCourse && name.is_empty() && code.is_empty() {
+                errors.push("no course name or code".to_string());
+            }
+
+            ImportRow {
+                line: row.line,
+                name,
+                code,
+                unit,
+                grade,
+                letter,
+                term: None,
+                target,
+                errors,
+            }
+        })
+        .collect()
+}
+
+/// Applies every valid row: catalog matches get their grade set and are included,
+/// everything else is added as an included user course. Rows with a term are enrolled in it.
+pub fn apply_import(app_state: &AppState, rows: &[ImportRow]) -> AppState {
+    let mut state = app_state.clone();
+    for row in rows.iter().filter(|row| row.is_valid()) {
+        let id = match row.target {
+            ImportTarget::Catalog(id) => EntryId::Catalog(id),
+            ImportTarget::NewCourse => state.add_user_course(Course {
+                code: row.code.clone(),
+                name: if row.name.is_empty() {
+                    row.code.clone()
+                } else {
+                    row.name.clone()
+                },
+                unit: row.unit,
+                ..Default::default()
+            }),
+        };
+        if let Some(entry) = state.entry_mut(id) {
+            entry.grade = row.grade;
+            entry.letter = row.letter.clone();
+            entry.included = true;
+            if let Some(term) = row.term {
+                entry.enrollment = Some(Enrollment {
+                    term,
+                    section: String::new(),
+                });
+            }
+        }
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<usize> {
+        let (_, rows) = parse_csv(text).unwrap();
+        rows.iter().map(|row| row.line).collect()
+    }
+
+    #[test]
+    fn rows_keep_their_file_line_across_blank_lines() {
+        assert_eq!(lines("code,grade\nCMPT 120,90\n\n,\nCMPT 225,80\n"), [2, 5]);
+    }
+
+    #[test]
+    fn multi_line_fields_count_from_where_the_row_starts() {
+        let text = "name,grade\n\"Data\nScience\",90\nAlgorithms,85\n";
+        assert_eq!(lines(text), [2, 4]);
+    }
+
+    fn plan(text: &str) -> Vec<ImportRow> {
+        let (headers, rows) = parse_csv(text).unwrap();
+        plan_import(&rows, &guess_mapping(&headers, &rows), &[])
+    }
+
+    #[test]
+    fn units_and_grades_follow_the_course_field_rules() {
+        let rows = plan("name,units,grade\nThesis,45,90\nSeminar,3,101%\nLab,3,88%\n");
+        assert_eq!(
+            rows[0].errors,
+            ["Units must be a whole number from 1 to 30 (got \"45\")"]
+        );
+        assert_eq!(
+            rows[1].errors,
+            ["Grade must be between 0 and 100 (got \"101%\")"]
+        );
+        assert!(rows[2].is_valid());
+        assert_eq!((rows[2].unit, rows[2].grade), (3, 88.0));
+    }
+
+    #[test]
+    fn plan_reports_the_file_line() {
+        let (headers, rows) = parse_csv("name,units,grade\n\nThesis,x,90\n").unwrap();
+        let plan = plan_import(&rows, &guess_mapping(&headers, &rows), &[]);
+        assert_eq!(plan[0].line, 3);
+        assert!(!plan[0].is_valid());
+    }
+}
@@ -2,13 +2,24 @@
 mod api;
 mod app;
 mod app_state;
+mod backup;
 mod components;
+mod export;
+mod history;
+mod hooks;
+mod import;
+mod logging;
 mod pages;
 mod route;
-mod types;
+mod share;
+pub mod storage;
+mod summary;
+mod sync;
+mod transcript;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen(start)]
 pub fn run_app() {
+    logging::init_from_url();
     yew::Renderer::<app::App>::new().render();
 }
@@ -0,0 +1,258 @@
+// src/logging.rs
+//
+// Leveled console logging. Each message is tagged with its target, the module that logged
+// it (e.g. `storage::indexed_db`), and is written only if the active filter lets that
+// level through for that target. Debug builds log up to `debug`; release builds only log
+// errors unless debug logging is switched on in the settings or with `?log=<filter>`.
+use std::cell::RefCell;
+use std::fmt;
+use std::str::FromStr;
+use web_sys::console;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        })
+    }
+}
+
+/// The most verbose level to log, or `None` for nothing.
+fn parse_level(s: &str) -> Result<Option<Level>, String> {
+    match s.trim().to_ascii_lowercase().as_str() {
+        "off" => Ok(None),
+        "error" => Ok(Some(Level::Error)),
+        "warn" => Ok(Some(Level::Warn)),
+        "info" => Ok(Some(Level::Info)),
+        "debug" => Ok(Some(Level::Debug)),
+        "trace" => Ok(Some(Level::Trace)),
+        other => Err(format!("unknown log level \"{}\"", other)),
+    }
+}
+
+/// Which messages are written, e.g. `debug` or `warn,storage=trace`: a default level and
+/// levels for targets, where a target also covers the modules below it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Filter {
+    default: Option<Level>,
+    targets: Vec<(String, Option<Level>)>,
+}
+
+impl Filter {
+    pub fn level(level: Option<Level>) -> Self {
+        Self {
+            default: level,
+            targets: vec![],
+        }
+    }
+
+    /// What this build logs when nothing else is asked for.
+    pub fn for_build() -> Self {
+        Self::level(Some(if cfg!(debug_assertions) {
+            Level::Debug
+        } else {
+            Level::Error
+        }))
+    }
+
+    pub fn enabled(&self, level: Level, target: &str) -> bool {
+        let covers = |prefix: &str| {
+            target == prefix
+                || target
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.starts_with("::"))
+        };
+        let max = self
+            .targets
+            .iter()
+            .filter(|(prefix, _)| covers(prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(self.default, |(_, level)| *level);
+        max.is_some_and(|max| level <= max)
+    }
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filter = Self::level(Some(Level::Error));
+        for part in s.split(',').filter(|part| !part.trim().is_empty()) {
+            match part.split_once('=') {
+                Some((target, level)) => filter
+                    .targets
+                    .push((target.trim().to_string(), parse_level(level)?)),
+                None => filter.default = parse_level(part)?,
+            }
+        }
+        Ok(filter)
+    }
+}
+
+thread_local! {
+    static FILTER: RefCell<Filter> = RefCell::new(Filter::for_build());
+    /// Set when the page was opened with `?log=`, which then wins over the settings.
+    static FROM_URL: RefCell<bool> = const { RefCell::new(false) };
+}
+
+/// Applies a `log` query parameter, if the page has one.
+pub fn init_from_url() {
+    let search = web_sys::window()
+        .and_then(|window| window.location().search().ok())
+        .unwrap_or_default();
+    let Some(spec) = search
+        .trim_start_matches('?')
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("log="))
+    else {
+        return;
+    };
+    let spec = js_sys::decode_uri_component(spec)
+        .map(String::from)
+        .unwrap_or_else(|_| spec.to_string());
+    match spec.parse::<Filter>() {
+        Ok(filter) => {
+            FILTER.with(|current| *current.borrow_mut() = filter);
+            FROM_URL.with(|from_url| *from_url.borrow_mut() = true);
+            info!("Logging with \"{}\" from the address", spec);
+        }
+        Err(err) => warn!("Ignoring ?log: {}", err),
+    }
+}
+
+/// Follows the debug logging setting, unless the URL chose a filter.
+pub fn set_debug(enabled: bool) {
+    if FROM_URL.with(|from_url| *from_url.borrow()) {
+        return;
+    }
+    let filter = if enabled {
+        Filter::level(Some(Level::Debug))
+    } else {
+        Filter::for_build()
+    };
+    FILTER.with(|current| *current.borrow_mut() = filter);
+}
+
+/// Writes a message if the filter allows it; use the macros below rather than calling this.
+pub fn write(level: Level, module: &str, args: fmt::Arguments) {
+    let target = module
+        .strip_prefix(concat!(env!("CARGO_CRATE_NAME"), "::"))
+        .unwrap_or(module);
+    if !FILTER.with(|filter| filter.borrow().enabled(level, target)) {
+        return;
+    }
+    let line = format!("[{} {}] {}", level, target, args);
+    // Native builds (the tests) have no browser console
+    if cfg!(not(target_arch = "wasm32")) {
+        eprintln!("{}", line);
+        return;
+    }
+    let line = line.into();
+    match level {
+        Level::Error => console::error_1(&line),
+        Level::Warn => console::warn_1(&line),
+        Level::Info => console::info_1(&line),
+        Level::Debug | Level::Trace => console::log_1(&line),
+    }
+}
+
+macro_rules! log {
+    ($level:ident, $($arg:tt)+) => {
+        $crate::logging::write(
+            $crate::logging::Level::$level,
+            module_path!(),
+            format_args!($($arg)+),
+        )
+    };
+}
+
+macro_rules! log_error {
+    ($($arg:tt)+) => { $crate::logging::log!(Error, $($arg)+) };
+}
+
+macro_rules! log_warn {
+    ($($arg:tt)+) => { $crate::logging::log!(Warn, $($arg)+) };
+}
+
+macro_rules! log_info {
+    ($($arg:tt)+) => { $crate::logging::log!(Info, $($arg)+) };
+}
+
+macro_rules! log_debug {
+    ($($arg:tt)+) => { $crate::logging::log!(Debug, $($arg)+) };
+}
+
+macro_rules! log_trace {
+    ($($arg:tt)+) => { $crate::logging::log!(Trace, $($arg)+) };
+}
+
+// Defined under longer names because `warn` would clash with the built-in attribute.
+pub(crate) use {
+    log, log_debug as debug, log_error as error, log_info as info, log_trace as trace,
+    log_warn as warn,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(spec: &str) -> Filter {
+        spec.parse().unwrap()
+    }
+
+    #[test]
+    fn a_bare_level_sets_the_default() {
+        let debug = filter("debug");
+        assert!(debug.enabled(Level::Debug, "app"));
+        assert!(!debug.enabled(Level::Trace, "app"));
+        assert_eq!(filter("DEBUG"), debug);
+    }
+
+    #[test]
+    fn the_default_is_errors_only() {
+        for spec in ["", " , ", "storage=trace"] {
+            let filter = filter(spec);
+            assert!(filter.enabled(Level::Error, "app"), "{}", spec);
+            assert!(!filter.enabled(Level::Warn, "app"), "{}", spec);
+        }
+    }
+
+    #[test]
+    fn off_logs_nothing() {
+        assert!(!filter("off").enabled(Level::Error, "app"));
+    }
+
+    #[test]
+    fn targets_override_the_default_for_their_modules() {
+        let filter = filter("warn, storage=trace, storage::indexed_db=off");
+        assert!(filter.enabled(Level::Trace, "storage"));
+        assert!(filter.enabled(Level::Trace, "storage::memory"));
+        assert!(!filter.enabled(Level::Error, "storage::indexed_db"));
+        // Only whole module names match
+        assert!(!filter.enabled(Level::Info, "storage_stats"));
+        assert!(filter.enabled(Level::Warn, "sync"));
+        assert!(!filter.enabled(Level::Info, "sync"));
+    }
+
+    #[test]
+    fn unknown_levels_are_rejected() {
+        assert_eq!(
+            "verbose".parse::<Filter>(),
+            Err("unknown log level \"verbose\"".to_string())
+        );
+        assert!("warn,storage=loud".parse::<Filter>().is_err());
+    }
+}
@@ -1,21 +1,23 @@
-use crate::api::get_course;
+use crate::api::{get_course, ApiError};
+use crate::app_state::{AppStateAction, AppStateContext, EntryId};
+use crate::components::{CourseImage, LoadError, PrereqGraph};
+use crate::logging::{debug, trace, warn};
 use crate::route::Route;
-use crate::types::Course;
-use anyhow::Error;
-use wasm_bindgen::JsValue;
-use web_sys::console;
+use gpa_core::types::{Course, Enrollment, Offering, Term};
 use yew::prelude::*;
 use yew_router::prelude::*;
 
 struct State {
     course: Option<Course>,
-    get_course_error: Option<Error>,
+    get_course_error: Option<ApiError>,
     get_course_loaded: bool,
 }
 
 pub struct CourseDetail {
     props: Props, // Now storing props
     state: State,
+    app_state: AppStateContext,
+    _context_listener: ContextHandle<AppStateContext>,
 }
 
 #[derive(Properties, Clone, PartialEq)]
@@ -26,7 +28,97 @@ pub struct Props {
 pub enum Msg {
     GetCourse,
     GetCourseSuccess(Course),
-    GetCourseError(Error),
+    GetCourseError(ApiError),
+    AppStateChanged(AppStateContext),
+    SelectSection(Term, String),
+    ClearSection,
+}
+
+impl CourseDetail {
+    fn view_offerings(
+        &self,
+        ctx: &Context<Self>,
+        course: &Course,
+        enrollment: Option<&Enrollment>,
+    ) -> Html {
+        if course.offerings.is_empty() {
+            return html! {
+                <div class="offerings_empty">{"No term offerings listed for this course."}</div>
+            };
+        }
+
+        let on_clear = ctx.link().callback(|_| Msg::ClearSection);
+        html! {
+            <div class="offerings">
+                <h3>{"Offerings"}</h3>
+                { for course.offerings.iter().map(|offering| self.view_offering(ctx, course, offering, enrollment)) }
+                if enrollment.is_some() {
+                    <button class="button-28" onclick={on_clear}>{"Clear selected section"}</button>
+                }
+            </div>
+        }
+    }
+
+    fn view_offering(
+        &self,
+        ctx: &Context<Self>,
+        course: &Course,
+        offering: &Offering,
+        enrollment: Option<&Enrollment>,
+    ) -> Html {
+        let term = offering.term;
+        let rows: Html = offering
+            .sections
+            .iter()
+            .map(|section| {
+                let selected = enrollment
+                    .map(|e| e.term == term && e.section == section.code)
+                    .unwrap_or(false);
+                let code = section.code.clone();
+                let on_select = ctx
+                    .link()
+                    .callback(move |_| Msg::SelectSection(term, code.clone()));
+                let meeting_times = section
+                    .meeting_times
+                    .iter()
+                    .map(|time| format!("{} {}–{} {}", time.days, time.start, time.end, time.room))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                html! {
+                    <tr class={classes!(selected.then_some("offering_selected"))}>
+                        <td>
+                            <input
+                                type="radio"
+                                name={format!("section-{}", course.id)}
+                                checked={selected}
+                                onclick={on_select}
+                            />
+                        </td>
+                        <td>{&section.code}</td>
+                        <td>{&section.instructor}</td>
+                        <td>{meeting_times.trim().to_string()}</td>
+                        <td>{&section.campus}</td>
+                    </tr>
+                }
+            })
+            .collect();
+
+        html! {
+            <div class="offering">
+                <div class="offering_term">{term.to_string()}</div>
+                <table class="offering_sections">
+                    <tr>
+                        <th>{"Took"}</th>
+                        <th>{"Section"}</th>
+                        <th>{"Instructor"}</th>
+                        <th>{"Meeting times"}</th>
+                        <th>{"Campus"}</th>
+                    </tr>
+                    {rows}
+                </table>
+            </div>
+        }
+    }
 }
 
 impl Component for CourseDetail {
@@ -34,7 +126,11 @@ impl Component for CourseDetail {
     type Properties = Props;
 
     fn create(ctx: &Context<Self>) -> Self {
-        console::log_1(&format!("CourseDetail created with id: {}", ctx.props().id).into());
+        trace!("CourseDetail created with id: {}", ctx.props().id);
+        let (app_state, context_listener) = ctx
+            .link()
+            .context::<AppStateContext>(ctx.link().callback(Msg::AppStateChanged))
+            .expect("No AppStateContext found");
         let instance = Self {
             props: ctx.props().clone(),
             state: State {
@@ -42,6 +138,8 @@ impl Component for CourseDetail {
                 get_course_error: None,
                 get_course_loaded: false,
             },
+            app_state,
+            _context_listener: context_listener,
         };
 
         ctx.link().send_message(Msg::GetCourse);
@@ -50,19 +148,20 @@ impl Component for CourseDetail {
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
-        console::log_1(&"Start of update function for course_detail".into());
         let res = true;
         match msg {
             Msg::GetCourse => {
+                self.state.get_course_loaded = false;
+                self.state.get_course_error = None;
                 let id = self.props.id;
-                console::log_1(&format!("Fetching course with id: {}", id).into());
+                debug!("Fetching course with id: {}", id);
 
-                let handler =
-                    ctx.link()
-                        .callback(move |result: Result<Course, Error>| match result {
-                            Ok(course) => Msg::GetCourseSuccess(course),
-                            Err(err) => Msg::GetCourseError(err),
-                        });
+                let handler = ctx
+                    .link()
+                    .callback(move |result: Result<Course, ApiError>| match result {
+                        Ok(course) => Msg::GetCourseSuccess(course),
+                        Err(err) => Msg::GetCourseError(err),
+                    });
 
                 get_course(id, handler);
             }
@@ -71,9 +170,25 @@ impl Component for CourseDetail {
                 self.state.get_course_loaded = true;
             }
             Msg::GetCourseError(error) => {
+                warn!("Could not load course {}: {}", self.props.id, error);
                 self.state.get_course_error = Some(error);
                 self.state.get_course_loaded = true;
             }
+            Msg::AppStateChanged(app_state) => {
+                self.app_state = app_state;
+            }
+            Msg::SelectSection(term, section) => {
+                self.app_state.dispatch(AppStateAction::SetEnrollment(
+                    EntryId::Catalog(self.props.id),
+                    Some(Enrollment { term, section }),
+                ));
+            }
+            Msg::ClearSection => {
+                self.app_state.dispatch(AppStateAction::SetEnrollment(
+                    EntryId::Catalog(self.props.id),
+                    None,
+                ));
+            }
         }
         res
     }
@@ -82,16 +197,24 @@ impl Component for CourseDetail {
         false
     }
 
-    fn view(&self, _ctx: &Context<Self>) -> Html {
-        console::log_1(&"Start of view function for course_detail".into());
+    fn view(&self, ctx: &Context<Self>) -> Html {
         if let Some(ref course) = self.state.course {
-            console::log_1(&"Rendering course details".into());
+            let enrollment = self
+                .app_state
+                .entry(EntryId::Catalog(course.id))
+                .and_then(|entry| entry.enrollment.as_ref());
             html! {
                 <div class="course_detail_container">
-                    <img class="course_detail_image" src={course.image.clone()}/>
+                    <CourseImage class="course_detail_image" course={course.clone()}/>
                     <div class="course_card_name">{&course.name}</div>
-                    <div class="course_card_price">{&course.teacher}</div>
+                    <div class="course_card_price">{course.instructor_for(enrollment)}</div>
                     <div style="margin: 10px 0; line-height: 24px;">{&course.description}</div>
+                    {self.view_offerings(ctx, course, enrollment)}
+                    <PrereqGraph
+                        course={course.clone()}
+                        catalog={self.app_state.catalog()}
+                        taken={self.app_state.included_codes()}
+                    />
 
                 <Link<Route> to={Route::HomePage } >
                     <button class="course_atc_button" >{"Return to MainPage"}</button>
@@ -105,16 +228,17 @@ impl Component for CourseDetail {
                     <div class="loading_spinner_text">{"Loading ..."}</div>
                 </div>
             }
-        } else {
-            if let Some(ref err) = self.state.get_course_error {
-                console::log_1(&JsValue::from(err.to_string()));
-            }
-
+        } else if let Some(error) = &self.state.get_course_error {
             html! {
-                <div>
-                    <span>{"Error loading course! :("}</span>
+                <div class="course_detail_container">
+                    <LoadError error={error.clone()} on_retry={ctx.link().callback(|_| Msg::GetCourse)} />
+                    <Link<Route> to={Route::HomePage}>
+                        <button class="course_atc_button">{"Return to MainPage"}</button>
+                    </Link<Route>>
                 </div>
             }
+        } else {
+            html! {}
         }
     }
 }
@@ -0,0 +1,488 @@
+// src/pages/data.rs
+use crate::app_state::{AppState, AppStateAction, AppStateContext, EntryId, SettingsContext};
+use crate::backup::Backup;
+use crate::components::LoadError;
+use crate::export::download;
+use crate::hooks::{use_catalog, CatalogStatus};
+use crate::import::{
+    apply_import, guess_mapping, parse_csv, plan_import, CsvRow, ImportField, ImportTarget,
+};
+use crate::route::Route;
+use crate::transcript::{parse_transcript, LineKind, Transcript};
+use std::rc::Rc;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+fn target_label(app_state: &AppState, target: &ImportTarget) -> String {
+    match target {
+        ImportTarget::Catalog(id) => match app_state.entry(EntryId::Catalog(*id)) {
+            Some(entry) => format!("Catalog: {}", entry.course.name),
+            None => "Catalog".to_string(),
+        },
+        ImportTarget::NewCourse => "New course".to_string(),
+    }
+}
+
+/// A CSV file that has been read but not yet imported.
+#[derive(Clone, PartialEq)]
+struct CsvUpload {
+    file_name: String,
+    headers: Vec<String>,
+    rows: Vec<CsvRow>,
+    mapping: Vec<ImportField>,
+}
+
+#[function_component(CsvImport)]
+fn csv_import() -> Html {
+    let app_state = use_context::<AppStateContext>().expect("No AppStateContext found");
+    let upload = use_state(|| None::<CsvUpload>);
+    let message = use_state(|| None::<String>);
+
+    let on_file = {
+        let upload = upload.clone();
+        let message = message.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let Some(file) = input.files().and_then(|files| files.get(0)) else {
+                return;
+            };
+            // So picking the same file again fires another change
+            input.set_value("");
+            let upload = upload.clone();
+            let message = message.clone();
+            spawn_local(async move {
+                let text = JsFuture::from(file.text())
+                    .await
+                    .ok()
+                    .and_then(|text| text.as_string())
+                    .unwrap_or_default();
+                match parse_csv(&text) {
+                    Ok((headers, rows)) => {
+                        let mapping = guess_mapping(&headers, &rows);
+                        upload.set(Some(CsvUpload {
+                            file_name: file.name(),
+                            headers,
+                            rows,
+                            mapping,
+                        }));
+                        message.set(None);
+                    }
+                    Err(err) => {
+                        upload.set(None);
+                        message.set(Some(format!("Could not read {}: {}", file.name(), err)));
+                    }
+                }
+            });
+        })
+    };
+
+    let Some(current) = (*upload).clone() else {
+        return html! {
+            <div class="data_section">
+                <h3>{"Import grades from CSV"}</h3>
+                <p>{"Choose a spreadsheet exported as CSV with a header row. You can map its columns before anything is imported."}</p>
+                <input type="file" accept=".csv,text/csv" onchange={on_file} />
+                if let Some(message) = (*message).clone() {
+                    <p class="data_message">{message}</p>
+                }
+            </div>
+        };
+    };
+
+    let preview = plan_import(&current.rows, &current.mapping, &app_state.catalog());
+    let valid = preview.iter().filter(|row| row.is_valid()).count();
+
+    let mapping_view: Html = current
+        .headers
+        .iter()
+        .enumerate()
+        .map(|(column, header)| {
+            let onchange = {
+                let upload = upload.clone();
+                let current = current.clone();
+                Callback::from(move |e: Event| {
+                    let select: HtmlSelectElement = e.target_unchecked_into();
+                    let mut next = current.clone();
+                    next.mapping[column] = ImportField::ALL[select.selected_index().max(0) as usize];
+                    upload.set(Some(next));
+                })
+            };
+            html! {
+                <label class="import_mapping">
+                    <span>{header}</span>
+                    <select {onchange}>
+                        { for ImportField::ALL.iter().map(|field| html! {
+                            <option selected={current.mapping[column] == *field}>{field.label()}</option>
+                        }) }
+                    </select>
+                </label>
+            }
+        })
+        .collect();
+
+    let preview_view: Html = preview
+        .iter()
+        .map(|row| {
+            let target = target_label(&app_state, &row.target);
+            html! {
+                <tr class={classes!((!row.is_valid()).then_some("import_row_invalid"))}>
+                    <td>{row.line}</td>
+                    <td>{&row.code}</td>
+                    <td>{&row.name}</td>
+                    <td>{row.unit}</td>
+                    <td>{row.grade}</td>
+                    <td>{&row.letter}</td>
+                    <td>{target}</td>
+                    <td>{row.errors.join("; ")}</td>
+                </tr>
+            }
+        })
+        .collect();
+
+    let on_import = {
+        let app_state = app_state.clone();
+        let upload = upload.clone();
+        let message = message.clone();
+        let preview = preview.clone();
+        Callback::from(move |_| {
+            app_state.dispatch(AppStateAction::Replace(apply_import(&app_state, &preview)));
+            let skipped = preview.len() - valid;
+            message.set(Some(format!(
+                "Imported {} row(s){}.",
+                valid,
+                if skipped > 0 {
+                    format!(", skipped {} with errors", skipped)
+                } else {
+                    String::new()
+                }
+            )));
+            upload.set(None);
+        })
+    };
+    let on_cancel = {
+        let upload = upload.clone();
+        Callback::from(move |_| upload.set(None))
+    };
+
+    html! {
+        <div class="data_section">
+            <h3>{format!("Import grades from {}", current.file_name)}</h3>
+            <div class="import_mappings">{mapping_view}</div>
+            <table class="import_preview">
+                <tr>
+                    <th>{"Line"}</th>
+                    <th>{"Code"}</th>
+                    <th>{"Name"}</th>
+                    <th>{"Units"}</th>
+                    <th>{"Percentage"}</th>
+                    <th>{"Letter"}</th>
+                    <th>{"Goes to"}</th>
+                    <th>{"Problems"}</th>
+                </tr>
+                {preview_view}
+            </table>
+            <p>{format!("{} of {} row(s) can be imported; rows with problems are skipped.", valid, preview.len())}</p>
+            <div class="export-buttons">
+                <button class="button-28" onclick={on_import} disabled={valid == 0}>{"Import"}</button>
+                <button class="button-28" onclick={on_cancel}>{"Cancel"}</button>
+            </div>
+        </div>
+    }
+}
+
+#[function_component(TranscriptImport)]
+fn transcript_import() -> Html {
+    let app_state = use_context::<AppStateContext>().expect("No AppStateContext found");
+    let text = use_state(String::new);
+    let transcript = use_state(|| None::<Rc<Transcript>>);
+    let message = use_state(|| None::<String>);
+
+    let on_input = {
+        let text = text.clone();
+        Callback::from(move |e: InputEvent| {
+            let area: HtmlTextAreaElement = e.target_unchecked_into();
+            text.set(area.value());
+        })
+    };
+    let on_read = {
+        let app_state = app_state.clone();
+        let text = text.clone();
+        let transcript = transcript.clone();
+        let message = message.clone();
+        Callback::from(move |_| {
+            transcript.set(Some(Rc::new(parse_transcript(&text, &app_state.catalog()))));
+            message.set(None);
+        })
+    };
+
+    let report = match (*transcript).clone() {
+        None => html! {},
+        Some(parsed) => {
+            let valid = parsed.rows.iter().filter(|row| row.is_valid()).count();
+            let on_import = {
+                let app_state = app_state.clone();
+                let text = text.clone();
+                let transcript = transcript.clone();
+                let message = message.clone();
+                let parsed = Rc::clone(&parsed);
+                Callback::from(move |_| {
+                    app_state.dispatch(AppStateAction::Replace(apply_import(
+                        &app_state,
+                        &parsed.rows,
+                    )));
+                    message.set(Some(format!(
+                        "Imported {} course(s) from the transcript.",
+                        valid
+                    )));
+                    transcript.set(None);
+                    text.set(String::new());
+                })
+            };
+            let lines_view: Html = parsed
+                .lines
+                .iter()
+                .map(|line| {
+                    let (status, invalid) = match &line.kind {
+                        LineKind::Term(term) => (format!("Term {}", term), false),
+                        LineKind::Course(index) => {
+                            let row = &parsed.rows[*index];
+                            let target = target_label(&app_state, &row.target);
+                            if row.is_valid() {
+                                (target, false)
+                            } else {
+                                (format!("Skipped: {}", row.errors.join("; ")), true)
+                            }
+                        }
+                        LineKind::Ignored => ("Ignored".to_string(), false),
+                        LineKind::Unrecognized(reason) => {
+                            (format!("Not recognized: {}", reason), true)
+                        }
+                    };
+                    html! {
+                        <tr class={classes!(invalid.then_some("import_row_invalid"))}>
+                            <td>{line.line}</td>
+                            <td class="transcript_text">{&line.text}</td>
+                            <td>{status}</td>
+                        </tr>
+                    }
+                })
+                .collect();
+            html! {
+                <>
+                    <table class="import_preview">
+                        <tr>
+                            <th>{"Line"}</th>
+                            <th>{"Text"}</th>
+                            <th>{"Result"}</th>
+                        </tr>
+                        {lines_view}
+                    </table>
+                    <p>{format!("{} of {} course(s) can be imported.", valid, parsed.rows.len())}</p>
+                    <div class="export-buttons">
+                        <button class="button-28" onclick={on_import} disabled={valid == 0}>{"Import"}</button>
+                    </div>
+                </>
+            }
+        }
+    };
+
+    html! {
+        <div class="data_section">
+            <h3>{"Import from goSFU transcript"}</h3>
+            <p>{"Open your unofficial transcript in goSFU, select all of its text, and paste it here."}</p>
+            <textarea class="transcript_input" rows="10" value={(*text).clone()} oninput={on_input} />
+            <div class="export-buttons">
+                <button class="button-28" onclick={on_read} disabled={text.trim().is_empty()}>{"Read transcript"}</button>
+            </div>
+            {report}
+            if let Some(message) = (*message).clone() {
+                <p class="data_message">{message}</p>
+            }
+        </div>
+    }
+}
+
+/// A backup that has been read and checked but not yet restored.
+struct PendingRestore {
+    file_name: String,
+    backup: Backup,
+    conflicts: Vec<String>,
+}
+
+#[function_component(BackupRestore)]
+fn backup_restore() -> Html {
+    let app_state = use_context::<AppStateContext>().expect("No AppStateContext found");
+    let settings = use_context::<SettingsContext>().expect("No SettingsContext found");
+    let pending = use_state(|| None::<Rc<PendingRestore>>);
+    let message = use_state(|| None::<String>);
+
+    let on_download = {
+        let app_state = app_state.clone();
+        let settings = settings.clone();
+        Callback::from(move |_| {
+            let backup = Backup::new(&app_state, &settings);
+            download(&backup.file_name(), "application/json", &backup.to_json());
+        })
+    };
+
+    let on_file = {
+        let app_state = app_state.clone();
+        let pending = pending.clone();
+        let message = message.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let Some(file) = input.files().and_then(|files| files.get(0)) else {
+                return;
+            };
+            input.set_value("");
+            let catalog = app_state.catalog();
+            let pending = pending.clone();
+            let message = message.clone();
+            spawn_local(async move {
+                let text = JsFuture::from(file.text())
+                    .await
+                    .ok()
+                    .and_then(|text| text.as_string())
+                    .unwrap_or_default();
+                match Backup::from_json(&text) {
+                    Ok(backup) => {
+                        let (_, conflicts) = backup.restore_onto(&catalog);
+                        pending.set(Some(Rc::new(PendingRestore {
+                            file_name: file.name(),
+                            backup,
+                            conflicts,
+                        })));
+                        message.set(None);
+                    }
+                    Err(err) => {
+                        pending.set(None);
+                        message.set(Some(format!("Could not restore {}: {}", file.name(), err)));
+                    }
+                }
+            });
+        })
+    };
+
+    let on_restore = {
+        let app_state = app_state.clone();
+        let settings = settings.clone();
+        let pending = pending.clone();
+        let message = message.clone();
+        Callback::from(move |_| {
+            let Some(restore) = (*pending).clone() else {
+                return;
+            };
+            let (state, _) = restore.backup.restore_onto(&app_state.catalog());
+            app_state.dispatch(AppStateAction::Replace(state));
+            settings.set(restore.backup.settings.clone());
+            message.set(Some(format!(
+                "Restored the backup made {}.",
+                restore.backup.created
+            )));
+            pending.set(None);
+        })
+    };
+    let on_cancel = {
+        let pending = pending.clone();
+        Callback::from(move |_| pending.set(None))
+    };
+
+    html! {
+        <div class="data_section">
+            <h3>{"Backup"}</h3>
+            <p>{"A backup holds every grade, included course, user course and setting. Restoring one replaces what is here now."}</p>
+            <div class="export-buttons">
+                <button class="button-28" onclick={on_download}>{"Download backup"}</button>
+            </div>
+            <label class="import_mapping">
+                <span>{"Restore from backup"}</span>
+                <input type="file" accept=".json,application/json" onchange={on_file} />
+            </label>
+            if let Some(restore) = (*pending).clone() {
+                <div class="backup_restore">
+                    <p>{format!(
+                        "{} was made {} with {} catalog and {} user course(s).",
+                        restore.file_name,
+                        restore.backup.created,
+                        restore.backup.state.catalog_entries().count(),
+                        restore.backup.state.user_entries().count()
+                    )}</p>
+                    if !restore.conflicts.is_empty() {
+                        <div class="plan_warnings">
+                            <div class="plan_warnings_title">{"The catalog has changed since this backup"}</div>
+                            <ul>
+                                { for restore.conflicts.iter().map(|conflict| html! { <li>{conflict}</li> }) }
+                            </ul>
+                        </div>
+                    }
+                    <div class="export-buttons">
+                        <button class="button-28" onclick={on_restore}>{"Restore"}</button>
+                        <button class="button-28" onclick={on_cancel}>{"Cancel"}</button>
+                    </div>
+                </div>
+            }
+            if let Some(message) = (*message).clone() {
+                <p class="data_message">{message}</p>
+            }
+        </div>
+    }
+}
+
+/// Turns on debug logging to the browser console, for bug reports.
+#[function_component(Diagnostics)]
+fn diagnostics() -> Html {
+    let settings = use_context::<SettingsContext>().expect("No SettingsContext found");
+    let on_toggle = {
+        let settings = settings.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut next = (*settings).clone();
+            next.debug_logging = input.checked();
+            settings.set(next);
+        })
+    };
+    html! {
+        <div class="data_section">
+            <h3>{"Diagnostics"}</h3>
+            <label>
+                <input type="checkbox" checked={settings.debug_logging} onchange={on_toggle} />
+                {" Log debug messages to the browser console"}
+            </label>
+            <p>{"For this visit only, add ?log=debug (or e.g. ?log=warn,storage=trace) to the address instead."}</p>
+        </div>
+    }
+}
+
+/// Imports and restores are matched against the catalog, so nothing is shown until it
+/// has loaded; on a fresh profile every row would otherwise become a new course.
+#[function_component(DataPage)]
+pub fn data_page() -> Html {
+    let (catalog, reload) = use_catalog();
+    let sections = match catalog {
+        CatalogStatus::Loading => html! {
+            <div>{"Loading ..."}</div>
+        },
+        CatalogStatus::Failed(error) => html! {
+            <LoadError {error} on_retry={reload} />
+        },
+        CatalogStatus::Loaded => html! {
+            <>
+                <CsvImport />
+                <TranscriptImport />
+                <BackupRestore />
+            </>
+        },
+    };
+    html! {
+        <div class="data_page">
+            <h2>{"Your data"}</h2>
+            { sections }
+            <Diagnostics />
+            <Link<Route> to={Route::HomePage}>
+                <button class="course_atc_button">{"Return to MainPage"}</button>
+            </Link<Route>>
+        </div>
+    }
+}
@@ -1,6 +1,17 @@
 mod home;
-pub use home::point_to_pa;
 pub use home::Home;
 
 mod course_detail;
 pub use course_detail::CourseDetail;
+
+mod user_course_detail;
+pub use user_course_detail::UserCourseDetail;
+
+mod data;
+pub use data::DataPage;
+
+mod shared;
+pub use shared::SharedView;
+
+mod report;
+pub use report::Report;
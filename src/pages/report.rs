@@ -0,0 +1,150 @@
+// src/pages/report.rs
+use crate::app_state::SettingsContext;
+use crate::hooks::use_gpa_summary;
+use crate::route::Route;
+use crate::summary::Contribution;
+use gpa_core::gpa::GpaTotals;
+use gpa_core::types::Term;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+/// Unit-weighted GPA of the given rows.
+fn gpa<'a>(rows: impl IntoIterator<Item = &'a Contribution>) -> (i32, f32) {
+    let totals: GpaTotals = rows
+        .into_iter()
+        .map(|row| (row.unit, row.grade_points))
+        .collect();
+    (totals.units, totals.gpa())
+}
+
+fn view_term(title: String, rows: &[&Contribution], cumulative: Option<(i32, f32)>) -> Html {
+    let (units, term_gpa) = gpa(rows.iter().copied());
+    html! {
+        <section class="report_term">
+            <h3>{title}</h3>
+            <table class="report_table">
+                <tr>
+                    <th>{"Course"}</th>
+                    <th>{"Title"}</th>
+                    <th class="report_number">{"Units"}</th>
+                    <th>{"Grade"}</th>
+                    <th class="report_number">{"Quality Points"}</th>
+                </tr>
+                { for rows.iter().map(|row| html! {
+                    <tr>
+                        <td>{&row.code}</td>
+                        <td>{&row.name}</td>
+                        <td class="report_number">{row.unit}</td>
+                        <td>{&row.letter}</td>
+                        <td class="report_number">{format!("{:.2}", row.quality_points)}</td>
+                    </tr>
+                }) }
+            </table>
+            <div class="report_totals">
+                <span>{format!("Term units: {}", units)}</span>
+                <span>{format!("Term GPA: {:.2}", term_gpa)}</span>
+                if let Some((cumulative_units, cumulative_gpa)) = cumulative {
+                    <span>{format!("Cumulative units: {}", cumulative_units)}</span>
+                    <span>{format!("Cumulative GPA: {:.2}", cumulative_gpa)}</span>
+                }
+            </div>
+        </section>
+    }
+}
+
+/// Transcript-style summary of the included courses, laid out for printing.
+#[function_component(Report)]
+pub fn report() -> Html {
+    let settings = use_context::<SettingsContext>().expect("No SettingsContext found");
+    let summary = use_gpa_summary();
+    let rows = &summary.courses;
+
+    let mut terms: Vec<Term> = rows.iter().filter_map(|row| row.term).collect();
+    terms.sort();
+    terms.dedup();
+
+    let mut taken: Vec<&Contribution> = vec![];
+    let terms_view: Html = terms
+        .iter()
+        .map(|&term| {
+            let in_term: Vec<&Contribution> =
+                rows.iter().filter(|row| row.term == Some(term)).collect();
+            taken.extend(&in_term);
+            view_term(term.to_string(), &in_term, Some(gpa(taken.iter().copied())))
+        })
+        .collect();
+    let unscheduled: Vec<&Contribution> = rows.iter().filter(|row| row.term.is_none()).collect();
+
+    let generated = String::from(
+        js_sys::Date::new_0().to_locale_date_string("en-CA", &wasm_bindgen::JsValue::UNDEFINED),
+    );
+
+    let on_name = {
+        let settings = settings.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut next = (*settings).clone();
+            next.student_name = input.value();
+            settings.set(next);
+        })
+    };
+    let on_program = {
+        let settings = settings.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut next = (*settings).clone();
+            next.program = input.value();
+            settings.set(next);
+        })
+    };
+    let on_print = Callback::from(|_| {
+        if let Some(window) = web_sys::window() {
+            let _ = window.print();
+        }
+    });
+
+    html! {
+        <div class="report">
+            <div class="report_controls no-print">
+                <label class="import_mapping">
+                    <span>{"Student name"}</span>
+                    <input type="text" value={settings.student_name.clone()} oninput={on_name} />
+                </label>
+                <label class="import_mapping">
+                    <span>{"Program"}</span>
+                    <input type="text" value={settings.program.clone()} oninput={on_program} />
+                </label>
+                <button class="button-28" onclick={on_print}>{"Print"}</button>
+                <Link<Route> to={Route::GPAView}>
+                    <button class="button-28">{"Back to overview"}</button>
+                </Link<Route>>
+            </div>
+
+            <header class="report_header">
+                <h2>{"Academic Record (Unofficial)"}</h2>
+                <dl>
+                    <dt>{"Student"}</dt>
+                    <dd>{ if settings.student_name.is_empty() { "—" } else { &settings.student_name } }</dd>
+                    <dt>{"Program"}</dt>
+                    <dd>{ if settings.program.is_empty() { "—" } else { &settings.program } }</dd>
+                    <dt>{"Generated"}</dt>
+                    <dd>{generated}</dd>
+                </dl>
+            </header>
+
+            if rows.is_empty() {
+                <p>{"No courses are included yet."}</p>
+            }
+            {terms_view}
+            if !unscheduled.is_empty() {
+                {view_term("No term selected".to_string(), &unscheduled, None)}
+            }
+
+            <footer class="report_summary">
+                <span>{format!("Total units: {}", summary.units_included)}</span>
+                <span>{format!("Cumulative GPA: {:.2}", summary.gpa)}</span>
+            </footer>
+        </div>
+    }
+}
@@ -0,0 +1,109 @@
+// src/pages/shared.rs
+use crate::api::{get_courses, ApiError};
+use crate::app_state::{AppState, AppStateAction, AppStateContext};
+use crate::components::OVERVIEW_COLUMNS;
+use crate::logging::error;
+use crate::route::Route;
+use crate::share::SharedPlan;
+use crate::summary::GpaSummary;
+use gpa_core::types::Course;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+/// Read-only view of a plan someone shared through a link.
+#[function_component(SharedView)]
+pub fn shared_view() -> Html {
+    let app_state = use_context::<AppStateContext>().expect("No AppStateContext found");
+    let navigator = use_navigator().expect("SharedView must be inside a router");
+    let plan = use_memo((), |_| {
+        let fragment = web_sys::window()
+            .and_then(|w| w.location().hash().ok())
+            .unwrap_or_default();
+        SharedPlan::decode(&fragment)
+    });
+
+    // The shared courses are matched against the catalog, so make sure it is loaded
+    {
+        let app_state = app_state.clone();
+        use_effect_with((), move |_| {
+            if app_state.catalog_entries().next().is_none() {
+                get_courses(Callback::from(
+                    move |result: Result<Vec<Course>, ApiError>| match result {
+                        Ok(courses) => app_state.dispatch(AppStateAction::LoadCatalog(courses)),
+                        Err(err) => error!("Could not load the catalog: {}", err),
+                    },
+                ));
+            }
+        });
+    }
+
+    let (plan, skipped) = match &*plan {
+        Ok((plan, skipped)) => (plan.clone(), skipped.clone()),
+        Err(err) => {
+            return html! {
+                <div class="data_page">
+                    <h2>{"Shared plan"}</h2>
+                    <p class="data_message">{format!("This plan cannot be opened: {}.", err)}</p>
+                    <Link<Route> to={Route::HomePage}>
+                        <button class="course_atc_button">{"Return to MainPage"}</button>
+                    </Link<Route>>
+                </div>
+            };
+        }
+    };
+
+    let shared_state = plan.merge_into(&AppState::default().with_catalog(app_state.catalog()));
+    let summary = GpaSummary::from_state(&shared_state);
+    let terms: Vec<String> = shared_state
+        .plan()
+        .iter()
+        .map(|planned| format!("{}: {}", planned.term, planned.course.name))
+        .collect();
+
+    let on_import = {
+        let app_state = app_state.clone();
+        Callback::from(move |_| {
+            app_state.dispatch(AppStateAction::Replace(plan.merge_into(&app_state)));
+            navigator.push(&Route::HomePage);
+        })
+    };
+
+    html! {
+        <div class="data_page">
+            <h2>{"Shared plan"}</h2>
+            <p>{"Someone shared this plan with you. It is shown read-only; nothing is saved unless you import it."}</p>
+            <table class="import_preview">
+                <tr>
+                    { for OVERVIEW_COLUMNS.iter().map(|column| html! { <th>{*column}</th> }) }
+                </tr>
+                { for summary.courses.iter().map(|row| html! {
+                    <tr>
+                        { for row.cells().into_iter().map(|cell| html! { <td>{cell}</td> }) }
+                    </tr>
+                }) }
+            </table>
+            <p class="navbar_value">{format!("GPA: {:.2}", summary.gpa)}</p>
+            if !skipped.is_empty() {
+                <div class="plan_warnings">
+                    <div class="plan_warnings_title">{"Left out because the link has invalid values"}</div>
+                    <ul>
+                        { for skipped.iter().map(|problem| html! { <li>{problem}</li> }) }
+                    </ul>
+                </div>
+            }
+            if !terms.is_empty() {
+                <ul class="shared_terms">
+                    { for terms.iter().map(|term| html! { <li>{term}</li> }) }
+                </ul>
+            }
+            <div class="export-buttons">
+                <button class="button-28" onclick={on_import} disabled={app_state.catalog_entries().next().is_none()}>
+                    {"Import into my data"}
+                </button>
+                <Link<Route> to={Route::HomePage}>
+                    <button class="button-28">{"Return to MainPage"}</button>
+                </Link<Route>>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,106 @@
+// src/pages/user_course_detail.rs
+use crate::app_state::{AppStateAction, AppStateContext, DetailField, EntryId};
+use crate::components::{CourseImage, FieldErrorMessage};
+use crate::route::Route;
+use gpa_core::validation::{parse_unit, Field, FieldError};
+use web_sys::{HtmlInputElement, HtmlTextAreaElement};
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub id: usize,
+}
+
+/// Detail page of a user-added course, where everything about it can be edited.
+#[function_component(UserCourseDetail)]
+pub fn user_course_detail(props: &Props) -> Html {
+    let app_state = use_context::<AppStateContext>().expect("No AppStateContext found");
+    let id = EntryId::User(props.id);
+    let unit_error = use_state(|| None::<FieldError>);
+
+    let Some(entry) = app_state.entry(id) else {
+        return html! {
+            <div class="course_detail_container">
+                <p>{"This course does not exist any more."}</p>
+                <Link<Route> to={Route::HomePage}>
+                    <button class="course_atc_button">{"Return to MainPage"}</button>
+                </Link<Route>>
+            </div>
+        };
+    };
+    let course = &entry.course;
+    let mut errors = entry.problems();
+    if let Some(error) = &*unit_error {
+        errors.retain(|problem| problem.field != Field::Unit);
+        errors.push(error.clone());
+    }
+
+    let on_name = {
+        let app_state = app_state.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            app_state.dispatch(AppStateAction::SetName(id, input.value()));
+        })
+    };
+    let on_unit = {
+        let app_state = app_state.clone();
+        let unit_error = unit_error.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            match parse_unit(&input.value()) {
+                Ok(unit) => {
+                    unit_error.set(None);
+                    app_state.dispatch(AppStateAction::SetUnit(id, unit));
+                }
+                Err(error) => unit_error.set(Some(error)),
+            }
+        })
+    };
+    let on_detail = |field: DetailField| {
+        let app_state = app_state.clone();
+        Callback::from(move |e: InputEvent| {
+            let value = match e.target_dyn_into::<HtmlTextAreaElement>() {
+                Some(area) => area.value(),
+                None => e.target_unchecked_into::<HtmlInputElement>().value(),
+            };
+            app_state.dispatch(AppStateAction::SetDetail(id, field, value));
+        })
+    };
+
+    html! {
+        <div class="course_detail_container user_course_detail">
+            <CourseImage class="course_detail_image" course={course.clone()}/>
+            <label class="import_mapping">
+                <span>{"Course name"}</span>
+                <input type="text" value={course.name.clone()} oninput={on_name} data-app-undo="true" />
+                <FieldErrorMessage errors={errors.clone()} field={Field::Name} />
+            </label>
+            <label class="import_mapping">
+                <span>{"Units"}</span>
+                <input type="text" value={course.unit.to_string()} oninput={on_unit} data-app-undo="true" />
+                <FieldErrorMessage errors={errors.clone()} field={Field::Unit} />
+            </label>
+            <label class="import_mapping">
+                <span>{"Teacher"}</span>
+                <input type="text" value={course.teacher.clone()} oninput={on_detail(DetailField::Teacher)} data-app-undo="true" />
+            </label>
+            <label class="import_mapping">
+                <span>{"Image URL"}</span>
+                <input type="text" value={course.image.clone()} oninput={on_detail(DetailField::Image)} data-app-undo="true" />
+            </label>
+            <label class="import_mapping">
+                <span>{"Description"}</span>
+                <textarea rows="4" value={course.description.clone()} oninput={on_detail(DetailField::Description)} />
+            </label>
+            <label class="import_mapping">
+                <span>{"Notes"}</span>
+                <textarea rows="6" value={entry.notes.clone()} oninput={on_detail(DetailField::Notes)} />
+            </label>
+
+            <Link<Route> to={Route::HomePage}>
+                <button class="course_atc_button">{"Return to MainPage"}</button>
+            </Link<Route>>
+        </div>
+    }
+}
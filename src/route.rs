@@ -5,9 +5,18 @@ use yew_router::prelude::*;
 pub enum Route {
     #[at("/courses/:id")]
     CourseDetail { id: usize },
+    /// A course the user added, by its user course number.
+    #[at("/my-courses/:id")]
+    UserCourseDetail { id: usize },
     #[at("/")]
     HomePage,
 
     #[at("/gpaview")]
     GPAView,
+    #[at("/data")]
+    Data,
+    #[at("/shared")]
+    Shared,
+    #[at("/report")]
+    Report,
 }
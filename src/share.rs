@@ -0,0 +1,207 @@
+// src/share.rs
+use crate::app_state::{same_course, AppState};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use gpa_core::grading::LETTER_GRADES;
+use gpa_core::types::{Course, Enrollment, Term};
+use gpa_core::validation::course_problems;
+use serde::{Deserialize, Serialize};
+
+/// Prefix of the URL fragment, bumped if the encoding ever changes.
+const FRAGMENT_PREFIX: &str = "v1.";
+/// Refuse fragments that inflate past this, so a crafted link cannot exhaust memory.
+const MAX_DECODED_BYTES: usize = 1 << 20;
+
+/// One included course in a shared plan.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct SharedCourse {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub code: String,
+    pub name: String,
+    pub unit: i32,
+    pub grade: f32,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub letter: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub term: Option<Term>,
+}
+
+impl SharedCourse {
+    /// The first reason this course cannot be merged, if any.
+    fn problem(&self) -> Option<String> {
+        if let Some(problem) = course_problems(&self.name, self.unit, self.grade).first() {
+            return Some(problem.to_string());
+        }
+        if !self.letter.is_empty() && !LETTER_GRADES.contains(&self.letter.as_str()) {
+            return Some(format!("Unknown letter grade \"{}\"", self.letter));
+        }
+        None
+    }
+}
+
+/// The included courses and grades of a plan, small enough to travel in a URL.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct SharedPlan {
+    pub courses: Vec<SharedCourse>,
+}
+
+impl SharedPlan {
+    /// Collects every included course, catalog and user-added.
+    pub fn from_state(state: &AppState) -> Self {
+        let courses = state
+            .included()
+            .map(|entry| SharedCourse {
+                code: entry.course.code.clone(),
+                name: entry.course.name.clone(),
+                unit: entry.course.unit,
+                grade: entry.grade,
+                letter: entry.letter.clone(),
+                term: entry.enrollment.as_ref().map(|e| e.term),
+            })
+            .collect();
+        Self { courses }
+    }
+
+    /// Deflates and base64-encodes the plan for a URL fragment.
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).expect("shared plan is always serializable");
+        let compressed = miniz_oxide::deflate::compress_to_vec(&json, 9);
+        format!("{}{}", FRAGMENT_PREFIX, URL_SAFE_NO_PAD.encode(compressed))
+    }
+
+    /// Reverses `encode`; accepts the fragment with or without its leading `#`. Links come
+    /// from outside the app, so courses the course fields would not accept are left out
+    /// and described in the returned messages.
+    pub fn decode(fragment: &str) -> Result<(Self, Vec<String>), String> {
+        let fragment = fragment.trim_start_matches('#');
+        let payload = fragment
+            .strip_prefix(FRAGMENT_PREFIX)
+            .ok_or_else(|| "the link does not contain a shared plan".to_string())?;
+        let compressed = URL_SAFE_NO_PAD
+            .decode(payload)
+            .map_err(|_| "the link is damaged (bad encoding)".to_string())?;
+        let json =
+            miniz_oxide::inflate::decompress_to_vec_with_limit(&compressed, MAX_DECODED_BYTES)
+                .map_err(|_| "the link is damaged (bad compression)".to_string())?;
+        let plan: Self = serde_json::from_slice(&json)
+            .map_err(|err| format!("the link is damaged ({})", err))?;
+
+        let mut skipped = vec![];
+        let courses = plan
+            .courses
+            .into_iter()
+            .enumerate()
+            .filter(|(index, course)| match course.problem() {
+                Some(problem) => {
+                    let label = [&course.code, &course.name]
+                        .into_iter()
+                        .find(|label| !label.trim().is_empty())
+                        .cloned()
+                        .unwrap_or_else(|| format!("Course {}", index + 1));
+                    skipped.push(format!("{}: {}", label, problem));
+                    false
+                }
+                None => true,
+            })
+            .map(|(_, course)| course)
+            .collect();
+        Ok((Self { courses }, skipped))
+    }
+
+    /// Full link to the shared view of this plan.
+    pub fn url(&self) -> String {
+        let origin = web_sys::window()
+            .and_then(|w| w.location().origin().ok())
+            .unwrap_or_default();
+        format!("{}/shared#{}", origin, self.encode())
+    }
+
+    /// `state` with the plan's courses included: courses found in the catalog get the
+    /// shared grade, the rest are appended as user courses.
+    pub fn merge_into(&self, state: &AppState) -> AppState {
+        let mut merged = state.clone();
+        for shared in &self.courses {
+            let course = Course {
+                code: shared.code.clone(),
+                name: shared.name.clone(),
+                unit: shared.unit,
+                ..Default::default()
+            };
+            let id = match state
+                .catalog_entries()
+                .find(|entry| same_course(&entry.course, &course))
+            {
+                Some(entry) => entry.id,
+                None => merged.add_user_course(course),
+            };
+            if let Some(entry) = merged.entry_mut(id) {
+                entry.grade = shared.grade;
+                entry.letter = shared.letter.clone();
+                entry.included = true;
+                if let Some(term) = shared.term {
+                    let section = entry
+                        .enrollment
+                        .as_ref()
+                        .filter(|e| e.term == term)
+                        .map(|e| e.section.clone())
+                        .unwrap_or_default();
+                    entry.enrollment = Some(Enrollment { term, section });
+                }
+            }
+        }
+        merged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shared(name: &str, unit: i32, grade: f32, letter: &str) -> SharedCourse {
+        SharedCourse {
+            code: String::new(),
+            name: name.to_string(),
+            unit,
+            grade,
+            letter: letter.to_string(),
+            term: None,
+        }
+    }
+
+    #[test]
+    fn round_trips_through_the_fragment() {
+        let plan = SharedPlan {
+            courses: vec![shared("Thesis", 6, 88.5, "A")],
+        };
+        let (decoded, skipped) = SharedPlan::decode(&format!("#{}", plan.encode())).unwrap();
+        assert_eq!(decoded, plan);
+        assert!(skipped.is_empty());
+    }
+
+    #[test]
+    fn skips_courses_the_course_fields_would_reject() {
+        let plan = SharedPlan {
+            courses: vec![
+                shared("Thesis", 6, 88.5, "A"),
+                shared("Free units", 0, 90.0, ""),
+                shared("Lots of units", 1_000_000_000, 90.0, ""),
+                shared("Extra credit", 3, 150.0, ""),
+                shared("", 3, 70.0, ""),
+                shared("Made up", 3, 70.0, "Z"),
+            ],
+        };
+        let (decoded, skipped) = SharedPlan::decode(&plan.encode()).unwrap();
+
+        assert_eq!(decoded.courses, [shared("Thesis", 6, 88.5, "A")]);
+        assert_eq!(skipped.len(), 5);
+        assert!(skipped[0].starts_with("Free units: Units"));
+        assert!(skipped[2].starts_with("Extra credit: Grade"));
+        assert!(skipped[3].starts_with("Course 5: "));
+    }
+
+    #[test]
+    fn rejects_fragments_without_a_plan() {
+        assert!(SharedPlan::decode("#somewhere").is_err());
+        assert!(SharedPlan::decode("#v1.!!!").is_err());
+    }
+}
@@ -0,0 +1,278 @@
+// src/storage/indexed_db.rs
+use super::{StorageError, Store, StoreFuture, StoreName, DB_NAME, SCHEMA_VERSION};
+use crate::logging::{error, warn};
+use js_sys::{Array, Promise};
+use std::cell::Cell;
+use std::rc::Rc;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    window, Event, IdbDatabase, IdbOpenDbRequest, IdbRequest, IdbTransaction, IdbTransactionMode,
+    IdbVersionChangeEvent,
+};
+
+/// localStorage keys written before IndexedDB was used, imported on first open.
+const LEGACY_STATE_KEY: &str = "app_state";
+const LEGACY_THEME_KEY: &str = "theme";
+
+pub struct IndexedDbStore {
+    db: IdbDatabase,
+}
+
+fn js_error(context: &str, err: JsValue) -> StorageError {
+    let detail = err
+        .dyn_ref::<js_sys::Error>()
+        .map(|e| String::from(e.message()))
+        .or_else(|| err.as_string())
+        .unwrap_or_else(|| format!("{:?}", err));
+    StorageError(format!("{}: {}", context, detail))
+}
+
+/// The request's error message, for rejecting its promise.
+fn request_error(request: &IdbRequest) -> JsValue {
+    request
+        .error()
+        .ok()
+        .flatten()
+        .map(|e| JsValue::from(e.message()))
+        .unwrap_or(JsValue::UNDEFINED)
+}
+
+/// Resolves with the request's result once it succeeds.
+async fn request_result(request: &IdbRequest) -> Result<JsValue, StorageError> {
+    let promise = Promise::new(&mut |resolve, reject| {
+        let succeeded = request.clone();
+        let onsuccess = Closure::once_into_js(move |_: Event| {
+            let result = succeeded.result().unwrap_or(JsValue::UNDEFINED);
+            let _ = resolve.call1(&JsValue::NULL, &result);
+        });
+        let failed = request.clone();
+        let onerror = Closure::once_into_js(move |_: Event| {
+            let _ = reject.call1(&JsValue::NULL, &request_error(&failed));
+        });
+        request.set_onsuccess(Some(onsuccess.unchecked_ref()));
+        request.set_onerror(Some(onerror.unchecked_ref()));
+    });
+    JsFuture::from(promise)
+        .await
+        .map_err(|err| js_error("request failed", err))
+}
+
+/// Resolves with the opened database. Other tabs close their connection when an upgrade
+/// asks them to; one that does not, such as a tab still running an older build, blocks
+/// the upgrade, and then this fails instead of leaving the app loading forever. Should
+/// the open still go through once that tab is closed, the late connection is closed.
+async fn open_result(request: &IdbOpenDbRequest) -> Result<IdbDatabase, StorageError> {
+    let settled = Rc::new(Cell::new(false));
+    let promise = Promise::new(&mut |resolve, reject| {
+        let succeeded = request.clone();
+        let late = settled.clone();
+        let onsuccess = Closure::once_into_js(move |_: Event| {
+            let result = succeeded.result().unwrap_or(JsValue::UNDEFINED);
+            if late.replace(true) {
+                if let Some(db) = result.dyn_ref::<IdbDatabase>() {
+                    db.close();
+                }
+            } else {
+                let _ = resolve.call1(&JsValue::NULL, &result);
+            }
+        });
+        let failed = request.clone();
+        let failed_settled = settled.clone();
+        let reject_blocked = reject.clone();
+        let onerror = Closure::once_into_js(move |_: Event| {
+            if !failed_settled.replace(true) {
+                let _ = reject.call1(&JsValue::NULL, &request_error(&failed));
+            }
+        });
+        let blocked_settled = settled.clone();
+        let onblocked = Closure::once_into_js(move |_: Event| {
+            if !blocked_settled.replace(true) {
+                let message = JsValue::from_str(
+                    "another tab has an older version of the app open; close it and reload",
+                );
+                let _ = reject_blocked.call1(&JsValue::NULL, &message);
+            }
+        });
+        request.set_onsuccess(Some(onsuccess.unchecked_ref()));
+        request.set_onerror(Some(onerror.unchecked_ref()));
+        request.set_onblocked(Some(onblocked.unchecked_ref()));
+    });
+    JsFuture::from(promise)
+        .await
+        .map(JsCast::unchecked_into)
+        .map_err(|err| js_error("cannot open database", err))
+}
+
+impl IndexedDbStore {
+    pub async fn open() -> Result<Self, StorageError> {
+        let factory = window()
+            .and_then(|w| w.indexed_db().ok().flatten())
+            .ok_or_else(|| StorageError("IndexedDB is not available".to_string()))?;
+        let request = factory
+            .open_with_u32(DB_NAME, SCHEMA_VERSION)
+            .map_err(|err| js_error("cannot open database", err))?;
+
+        // Must stay alive until the open request settles.
+        let upgrade_request = request.clone();
+        let onupgradeneeded = Closure::<dyn FnMut(IdbVersionChangeEvent)>::new(
+            move |event: IdbVersionChangeEvent| {
+                let db: IdbDatabase = match upgrade_request.result() {
+                    Ok(db) => db.unchecked_into(),
+                    Err(_) => return,
+                };
+                let Some(transaction) = upgrade_request.transaction() else {
+                    return;
+                };
+                if let Err(err) = Self::upgrade(&db, &transaction, event.old_version() as u32) {
+                    error!("{}", err);
+                    let _ = transaction.abort();
+                }
+            },
+        );
+        request.set_onupgradeneeded(Some(onupgradeneeded.as_ref().unchecked_ref()));
+
+        let db = open_result(&request).await;
+        request.set_onupgradeneeded(None);
+        drop(onupgradeneeded);
+        let db = db?;
+
+        // A tab running a newer version needs this connection closed before it can
+        // upgrade the schema; this tab stops saving until it is reloaded.
+        let closing = db.clone();
+        let onversionchange = Closure::once_into_js(move |_: IdbVersionChangeEvent| {
+            warn!("A newer version of the app was opened in another tab; reload to keep saving");
+            closing.close();
+        });
+        db.set_onversionchange(Some(onversionchange.unchecked_ref()));
+        Ok(Self { db })
+    }
+
+    /// Brings the schema from `old_version` to `SCHEMA_VERSION`, one step at a time.
+    fn upgrade(
+        db: &IdbDatabase,
+        transaction: &IdbTransaction,
+        old_version: u32,
+    ) -> Result<(), StorageError> {
+        for version in (old_version + 1)..=SCHEMA_VERSION {
+            match version {
+                1 => {
+                    for store in [
+                        StoreName::Snapshots,
+                        StoreName::Scenarios,
+                        StoreName::Settings,
+                    ] {
+                        db.create_object_store(store.as_str())
+                            .map_err(|err| js_error("cannot create object store", err))?;
+                    }
+                    Self::import_local_storage(transaction)?;
+                }
+                _ => {
+                    return Err(StorageError(format!(
+                        "no upgrade step for schema version {}",
+                        version
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves data saved in localStorage by earlier versions of the app into the database.
+    /// The old keys are removed only once the upgrade has committed, so an aborted
+    /// upgrade leaves them for the next attempt.
+    fn import_local_storage(transaction: &IdbTransaction) -> Result<(), StorageError> {
+        let Some(local) = window().and_then(|w| w.local_storage().ok().flatten()) else {
+            return Ok(());
+        };
+        let mut imported = vec![];
+        if let Ok(Some(state)) = local.get_item(LEGACY_STATE_KEY) {
+            Self::put_in(transaction, StoreName::Snapshots, "current", &state)?;
+            imported.push(LEGACY_STATE_KEY);
+        }
+        if let Ok(Some(theme)) = local.get_item(LEGACY_THEME_KEY) {
+            let settings = serde_json::json!({ "theme": theme }).to_string();
+            Self::put_in(transaction, StoreName::Settings, "settings", &settings)?;
+            imported.push(LEGACY_THEME_KEY);
+        }
+        if !imported.is_empty() {
+            let oncomplete = Closure::once_into_js(move |_: Event| {
+                for key in imported {
+                    let _ = local.remove_item(key);
+                }
+            });
+            transaction.set_oncomplete(Some(oncomplete.unchecked_ref()));
+        }
+        Ok(())
+    }
+
+    fn put_in(
+        transaction: &IdbTransaction,
+        store: StoreName,
+        key: &str,
+        value: &str,
+    ) -> Result<(), StorageError> {
+        transaction
+            .object_store(store.as_str())
+            .and_then(|object_store| {
+                object_store.put_with_key(&JsValue::from_str(value), &JsValue::from_str(key))
+            })
+            .map(|_| ())
+            .map_err(|err| js_error("cannot write during upgrade", err))
+    }
+
+    fn object_store(
+        &self,
+        store: StoreName,
+        mode: IdbTransactionMode,
+    ) -> Result<web_sys::IdbObjectStore, StorageError> {
+        self.db
+            .transaction_with_str_and_mode(store.as_str(), mode)
+            .and_then(|transaction| transaction.object_store(store.as_str()))
+            .map_err(|err| js_error(store.as_str(), err))
+    }
+}
+
+impl Store for IndexedDbStore {
+    fn get<'a>(&'a self, store: StoreName, key: &'a str) -> StoreFuture<'a, Option<String>> {
+        Box::pin(async move {
+            let request = self
+                .object_store(store, IdbTransactionMode::Readonly)?
+                .get(&JsValue::from_str(key))
+                .map_err(|err| js_error("get", err))?;
+            Ok(request_result(&request).await?.as_string())
+        })
+    }
+
+    fn put<'a>(&'a self, store: StoreName, key: &'a str, value: String) -> StoreFuture<'a, ()> {
+        Box::pin(async move {
+            let request = self
+                .object_store(store, IdbTransactionMode::Readwrite)?
+                .put_with_key(&JsValue::from_str(&value), &JsValue::from_str(key))
+                .map_err(|err| js_error("put", err))?;
+            request_result(&request).await.map(|_| ())
+        })
+    }
+
+    fn delete<'a>(&'a self, store: StoreName, key: &'a str) -> StoreFuture<'a, ()> {
+        Box::pin(async move {
+            let request = self
+                .object_store(store, IdbTransactionMode::Readwrite)?
+                .delete(&JsValue::from_str(key))
+                .map_err(|err| js_error("delete", err))?;
+            request_result(&request).await.map(|_| ())
+        })
+    }
+
+    fn keys(&self, store: StoreName) -> StoreFuture<'_, Vec<String>> {
+        Box::pin(async move {
+            let request = self
+                .object_store(store, IdbTransactionMode::Readonly)?
+                .get_all_keys()
+                .map_err(|err| js_error("keys", err))?;
+            let keys: Array = request_result(&request).await?.unchecked_into();
+            Ok(keys.iter().filter_map(|key| key.as_string()).collect())
+        })
+    }
+}
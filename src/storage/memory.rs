@@ -0,0 +1,40 @@
+// src/storage/memory.rs
+use super::{StorageError, Store, StoreFuture, StoreName};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+/// Keeps everything in a map; used in tests and when IndexedDB is unavailable.
+#[derive(Default)]
+pub struct MemoryStore {
+    data: RefCell<BTreeMap<(StoreName, String), String>>,
+}
+
+impl Store for MemoryStore {
+    fn get<'a>(&'a self, store: StoreName, key: &'a str) -> StoreFuture<'a, Option<String>> {
+        let value = self.data.borrow().get(&(store, key.to_string())).cloned();
+        Box::pin(async move { Ok::<_, StorageError>(value) })
+    }
+
+    fn put<'a>(&'a self, store: StoreName, key: &'a str, value: String) -> StoreFuture<'a, ()> {
+        self.data
+            .borrow_mut()
+            .insert((store, key.to_string()), value);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn delete<'a>(&'a self, store: StoreName, key: &'a str) -> StoreFuture<'a, ()> {
+        self.data.borrow_mut().remove(&(store, key.to_string()));
+        Box::pin(async { Ok(()) })
+    }
+
+    fn keys(&self, store: StoreName) -> StoreFuture<'_, Vec<String>> {
+        let keys = self
+            .data
+            .borrow()
+            .keys()
+            .filter(|(name, _)| *name == store)
+            .map(|(_, key)| key.clone())
+            .collect();
+        Box::pin(async move { Ok(keys) })
+    }
+}
@@ -0,0 +1,204 @@
+// src/storage/mod.rs
+//
+// Key-value persistence for snapshots, scenarios and settings. Values are stored as JSON
+// strings so every backend holds exactly the same data.
+mod indexed_db;
+mod memory;
+pub use indexed_db::IndexedDbStore;
+pub use memory::MemoryStore;
+
+use crate::logging::warn;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+/// Name of the IndexedDB database.
+pub const DB_NAME: &str = "gpacalculator";
+/// Current schema version; bump it and add a step to `IndexedDbStore::upgrade` when the
+/// set of object stores changes.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum StoreName {
+    /// The working state, and anything else captured at a point in time.
+    Snapshots,
+    /// Named what-if copies of the state.
+    Scenarios,
+    Settings,
+}
+
+impl StoreName {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StoreName::Snapshots => "snapshots",
+            StoreName::Scenarios => "scenarios",
+            StoreName::Settings => "settings",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct StorageError(pub String);
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "storage error: {}", self.0)
+    }
+}
+
+pub type StoreFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, StorageError>> + 'a>>;
+
+/// A storage backend.
+pub trait Store {
+    fn get<'a>(&'a self, store: StoreName, key: &'a str) -> StoreFuture<'a, Option<String>>;
+    fn put<'a>(&'a self, store: StoreName, key: &'a str, value: String) -> StoreFuture<'a, ()>;
+    fn delete<'a>(&'a self, store: StoreName, key: &'a str) -> StoreFuture<'a, ()>;
+    fn keys(&self, store: StoreName) -> StoreFuture<'_, Vec<String>>;
+}
+
+/// Shared handle to whichever backend is in use, with typed helpers on top.
+#[derive(Clone)]
+pub struct Storage(Rc<dyn Store>);
+
+impl PartialEq for Storage {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Storage {
+    pub fn new(store: impl Store + 'static) -> Self {
+        Self(Rc::new(store))
+    }
+
+    /// Opens IndexedDB, falling back to memory (nothing survives a reload) when it is
+    /// unavailable, e.g. in some private browsing modes.
+    pub async fn open() -> Self {
+        match IndexedDbStore::open().await {
+            Ok(store) => Self::new(store),
+            Err(err) => {
+                warn!("{}; keeping data in memory only", err);
+                Self::new(MemoryStore::default())
+            }
+        }
+    }
+
+    pub async fn load<T: DeserializeOwned>(
+        &self,
+        store: StoreName,
+        key: &str,
+    ) -> Result<Option<T>, StorageError> {
+        match self.0.get(store, key).await? {
+            Some(json) => serde_json::from_str(&json)
+                .map(Some)
+                .map_err(|err| StorageError(format!("{}/{}: {}", store.as_str(), key, err))),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn save<T: Serialize>(
+        &self,
+        store: StoreName,
+        key: &str,
+        value: &T,
+    ) -> Result<(), StorageError> {
+        let json = serde_json::to_string(value).map_err(|err| StorageError(err.to_string()))?;
+        self.0.put(store, key, json).await
+    }
+
+    pub async fn delete(&self, store: StoreName, key: &str) -> Result<(), StorageError> {
+        self.0.delete(store, key).await
+    }
+
+    pub async fn keys(&self, store: StoreName) -> Result<Vec<String>, StorageError> {
+        self.0.keys(store).await
+    }
+}
+
+/// Runs a future that finishes without waiting, as every `MemoryStore` operation does.
+#[cfg(test)]
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    use std::task::{Context, Poll, Waker};
+    let mut context = Context::from_waker(Waker::noop());
+    match std::pin::pin!(future).poll(&mut context) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("future did not finish immediately"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Value {
+        name: String,
+        count: u32,
+    }
+
+    fn value(name: &str, count: u32) -> Value {
+        Value {
+            name: name.to_string(),
+            count,
+        }
+    }
+
+    #[test]
+    fn saves_and_loads_typed_values() {
+        let storage = Storage::new(MemoryStore::default());
+        block_on(storage.save(StoreName::Settings, "a", &value("a", 1))).unwrap();
+        block_on(storage.save(StoreName::Settings, "a", &value("a", 2))).unwrap();
+
+        let loaded = block_on(storage.load::<Value>(StoreName::Settings, "a")).unwrap();
+        assert_eq!(loaded, Some(value("a", 2)));
+        let missing = block_on(storage.load::<Value>(StoreName::Settings, "b")).unwrap();
+        assert_eq!(missing, None);
+    }
+
+    #[test]
+    fn stores_are_separate() {
+        let storage = Storage::new(MemoryStore::default());
+        block_on(storage.save(StoreName::Scenarios, "plan", &value("plan", 1))).unwrap();
+
+        let other = block_on(storage.load::<Value>(StoreName::Snapshots, "plan")).unwrap();
+        assert_eq!(other, None);
+        assert_eq!(
+            block_on(storage.keys(StoreName::Snapshots)).unwrap(),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn lists_keys_in_order_and_deletes() {
+        let storage = Storage::new(MemoryStore::default());
+        for name in ["b", "a", "c"] {
+            block_on(storage.save(StoreName::Scenarios, name, &value(name, 0))).unwrap();
+        }
+        assert_eq!(
+            block_on(storage.keys(StoreName::Scenarios)).unwrap(),
+            ["a", "b", "c"]
+        );
+
+        block_on(storage.delete(StoreName::Scenarios, "b")).unwrap();
+        block_on(storage.delete(StoreName::Scenarios, "missing")).unwrap();
+        assert_eq!(
+            block_on(storage.keys(StoreName::Scenarios)).unwrap(),
+            ["a", "c"]
+        );
+        let deleted = block_on(storage.load::<Value>(StoreName::Scenarios, "b")).unwrap();
+        assert_eq!(deleted, None);
+    }
+
+    #[test]
+    fn unreadable_values_name_the_store_and_key() {
+        let storage = Storage::new(MemoryStore::default());
+        block_on(storage.save(StoreName::Settings, "settings", &"not an object")).unwrap();
+
+        let err = block_on(storage.load::<Value>(StoreName::Settings, "settings")).unwrap_err();
+        assert!(err.0.starts_with("settings/settings: "), "{}", err);
+    }
+}
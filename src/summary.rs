@@ -0,0 +1,193 @@
+// src/summary.rs
+use crate::app_state::{AppState, EntryId};
+use gpa_core::gpa::GpaTotals;
+use gpa_core::grading::{point_to_letter, point_to_pa};
+use gpa_core::prereq::course_label;
+use gpa_core::types::Term;
+use gpa_core::validation::FieldError;
+
+/// What one included course adds to the GPA.
+#[derive(Clone, PartialEq)]
+pub struct Contribution {
+    pub id: EntryId,
+    pub name: String,
+    pub code: String,
+    pub unit: i32,
+    pub grade: f32,
+    pub letter: String,
+    pub grade_points: f32,
+    pub quality_points: f32,
+    /// Term the course is enrolled in, if one was chosen.
+    pub term: Option<Term>,
+}
+
+impl Contribution {
+    /// The formatted cells, in `OVERVIEW_COLUMNS` order.
+    pub fn cells(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.code.clone(),
+            self.unit.to_string(),
+            self.grade.to_string(),
+            self.letter.clone(),
+            format!("{:.2}", self.grade_points),
+            format!("{:.2}", self.quality_points),
+        ]
+    }
+}
+
+/// An included course left out of the GPA because some of its values are invalid.
+#[derive(Clone, PartialEq)]
+pub struct Excluded {
+    pub id: EntryId,
+    pub label: String,
+    pub problems: Vec<FieldError>,
+}
+
+/// Everything derived from the grades, computed in one place for every view.
+#[derive(Clone, PartialEq, Default)]
+pub struct GpaSummary {
+    pub gpa: f32,
+    /// Units of every course with a grade, whether or not it is included.
+    pub units_attempted: i32,
+    /// Units counted towards the GPA.
+    pub units_included: i32,
+    pub quality_points: f32,
+    /// Included courses, fetched then user-added.
+    pub courses: Vec<Contribution>,
+    /// Included courses that do not count because of invalid values.
+    pub excluded: Vec<Excluded>,
+}
+
+impl GpaSummary {
+    pub fn from_state(app_state: &AppState) -> Self {
+        let units_attempted = app_state
+            .entries
+            .iter()
+            .filter(|entry| !entry.letter.is_empty())
+            .map(|entry| entry.course.unit)
+            .sum();
+
+        let mut totals = GpaTotals::default();
+        let mut courses = vec![];
+        let mut excluded = vec![];
+        for entry in app_state.included() {
+            let problems = entry.problems();
+            if !problems.is_empty() {
+                let label = course_label(&entry.course).trim();
+                excluded.push(Excluded {
+                    id: entry.id,
+                    label: if label.is_empty() {
+                        "Unnamed course".to_string()
+                    } else {
+                        label.to_string()
+                    },
+                    problems,
+                });
+                continue;
+            }
+            let grade_points = point_to_pa(entry.grade);
+            let unit = entry.course.unit;
+            totals.add(unit, grade_points);
+
+            let letter = if entry.letter.is_empty() {
+                point_to_letter(entry.grade)
+            } else {
+                entry.letter.clone()
+            };
+            courses.push(Contribution {
+                id: entry.id,
+                name: entry.course.name.clone(),
+                code: entry.course.code.clone(),
+                unit,
+                grade: entry.grade,
+                letter,
+                grade_points,
+                quality_points: grade_points * unit as f32,
+                term: entry.enrollment.as_ref().map(|e| e.term),
+            });
+        }
+
+        Self {
+            gpa: totals.gpa(),
+            units_attempted,
+            units_included: totals.units,
+            quality_points: totals.quality_points,
+            courses,
+            excluded,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_state::CourseEntry;
+    use gpa_core::types::Course;
+    use std::rc::Rc;
+
+    fn entry(id: EntryId, code: &str, unit: i32, grade: f32, included: bool) -> CourseEntry {
+        let course = Course {
+            code: code.to_string(),
+            name: format!("{} course", code),
+            unit,
+            ..Course::default()
+        };
+        let mut entry = CourseEntry::new(id, course);
+        entry.grade = grade;
+        entry.letter = point_to_letter(grade);
+        entry.included = included;
+        entry
+    }
+
+    fn summary(entries: Vec<CourseEntry>) -> GpaSummary {
+        let mut state = AppState::default();
+        state.entries = Rc::new(entries);
+        GpaSummary::from_state(&state)
+    }
+
+    #[test]
+    fn empty_state_has_no_gpa() {
+        let summary = summary(vec![]);
+        assert_eq!(summary.gpa, 0.0);
+        assert_eq!((summary.units_attempted, summary.units_included), (0, 0));
+        assert!(summary.courses.is_empty() && summary.excluded.is_empty());
+    }
+
+    #[test]
+    fn gpa_is_weighted_by_units_across_catalog_and_user_courses() {
+        let summary = summary(vec![
+            entry(EntryId::Catalog(0), "CMPT 726", 3, 97.0, true), // A+, 4.33
+            entry(EntryId::User(0), "CMPT 999", 6, 77.0, true),    // B, 3.00
+        ]);
+        let expected = (4.33 * 3.0 + 3.00 * 6.0) / 9.0;
+        assert!((summary.gpa - expected).abs() < 1e-5, "{}", summary.gpa);
+        assert_eq!(summary.units_included, 9);
+        assert!((summary.quality_points - (4.33 * 3.0 + 18.0)).abs() < 1e-4);
+        let ids: Vec<EntryId> = summary.courses.iter().map(|c| c.id).collect();
+        assert_eq!(ids, [EntryId::Catalog(0), EntryId::User(0)]);
+        assert_eq!(summary.courses[1].cells()[6], "18.00");
+    }
+
+    #[test]
+    fn courses_left_out_do_not_count() {
+        let mut unnamed = entry(EntryId::User(1), "", 0, 90.0, true);
+        unnamed.course.name = String::new();
+        let summary = summary(vec![
+            entry(EntryId::Catalog(0), "CMPT 726", 3, 90.0, true),
+            entry(EntryId::Catalog(1), "CMPT 732", 6, 50.0, false),
+            unnamed,
+        ]);
+        assert_eq!(summary.gpa, 4.00);
+        assert_eq!(summary.units_included, 3);
+        // Graded but not included still counts as attempted
+        assert_eq!(summary.units_attempted, 9);
+        assert_eq!(summary.courses.len(), 1);
+
+        assert_eq!(summary.excluded.len(), 1);
+        let excluded = &summary.excluded[0];
+        assert_eq!(excluded.id, EntryId::User(1));
+        assert_eq!(excluded.label, "Unnamed course");
+        assert_eq!(excluded.problems.len(), 2);
+    }
+}
@@ -0,0 +1,101 @@
+// src/sync.rs
+use crate::app_state::{AppState, CourseEntry, Settings};
+use crate::logging::{error, warn};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{BroadcastChannel, MessageEvent};
+
+const CHANNEL_NAME: &str = "gpacalculator-sync";
+
+type MessageListener = Closure<dyn FnMut(MessageEvent)>;
+
+/// A change made in one tab, sent to every other open tab.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum SyncMessage {
+    State(AppState),
+    Settings(Settings),
+}
+
+/// Keeps tabs of the app in step. Each tab publishes its changes after saving them and
+/// merges what the others publish. What a tab applied is not published back, so two tabs
+/// never bounce the same change between them.
+pub struct TabSync {
+    channel: BroadcastChannel,
+    last_received: Rc<RefCell<Option<SyncMessage>>>,
+    /// The entries this tab last published or received, i.e. last had in common with the
+    /// others; incoming states are merged against them.
+    shared: Rc<RefCell<Option<Rc<Vec<CourseEntry>>>>>,
+    listener: RefCell<Option<MessageListener>>,
+}
+
+impl TabSync {
+    /// `None` where the browser has no BroadcastChannel; tabs then work independently.
+    pub fn open() -> Option<Self> {
+        let channel = BroadcastChannel::new(CHANNEL_NAME).ok()?;
+        Some(Self {
+            channel,
+            last_received: Rc::new(RefCell::new(None)),
+            shared: Rc::new(RefCell::new(None)),
+            listener: RefCell::new(None),
+        })
+    }
+
+    /// Calls `apply` with every change published by another tab, together with the entries
+    /// to merge a state against (`None` before anything was shared).
+    pub fn listen(&self, apply: impl Fn(SyncMessage, Option<Rc<Vec<CourseEntry>>>) + 'static) {
+        let last_received = Rc::clone(&self.last_received);
+        let shared = Rc::clone(&self.shared);
+        let listener = MessageListener::new(move |event: MessageEvent| {
+            let Some(text) = event.data().as_string() else {
+                return;
+            };
+            match serde_json::from_str::<SyncMessage>(&text) {
+                Ok(message) => {
+                    *last_received.borrow_mut() = Some(message.clone());
+                    let base = match &message {
+                        SyncMessage::State(state) => shared.replace(Some(state.entries.clone())),
+                        SyncMessage::Settings(_) => shared.borrow().clone(),
+                    };
+                    apply(message, base);
+                }
+                Err(err) => warn!("Ignoring sync message: {}", err),
+            }
+        });
+        self.channel
+            .set_onmessage(Some(listener.as_ref().unchecked_ref()));
+        *self.listener.borrow_mut() = Some(listener);
+    }
+
+    /// Whether `message` is the change this tab just received, rather than its own edit.
+    /// Answers yes only once, so making the same edit again later is still published.
+    pub fn take_echo(&self, message: &SyncMessage) -> bool {
+        let mut last_received = self.last_received.borrow_mut();
+        if last_received.as_ref() == Some(message) {
+            *last_received = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Sends a change made in this tab to the other tabs.
+    pub fn publish(&self, message: &SyncMessage) {
+        if let SyncMessage::State(state) = message {
+            *self.shared.borrow_mut() = Some(state.entries.clone());
+        }
+        let text = serde_json::to_string(message).expect("sync message is always serializable");
+        if let Err(err) = self.channel.post_message(&text.into()) {
+            error!("Could not publish a change to other tabs: {:?}", err);
+        }
+    }
+}
+
+impl Drop for TabSync {
+    fn drop(&mut self) {
+        self.channel.set_onmessage(None);
+        self.channel.close();
+    }
+}
@@ -0,0 +1,294 @@
+// src/transcript.rs
+//
+// Reads the text of a goSFU unofficial transcript, as copied from the browser. Terms appear
+// as headings ("Fall 2023") followed by one line per course:
+//
+//     CMPT 726   Machine Learning   3.00   A-   11.01
+//
+// i.e. subject and number, title, units, grade and optionally the grade points.
+use crate::import::{find_in_catalog, ImportRow, ImportTarget};
+use gpa_core::grading::{letter_to_point, LETTER_GRADES};
+use gpa_core::types::{Course, Term};
+use gpa_core::validation::{parse_unit, FieldError};
+
+/// How one line of the pasted text was understood.
+#[derive(Clone, PartialEq, Debug)]
+pub enum LineKind {
+    Term(Term),
+    /// Index into `Transcript::rows`.
+    Course(usize),
+    /// Headings, totals and other text a transcript is known to contain.
+    Ignored,
+    Unrecognized(String),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TranscriptLine {
+    /// 1-based line number in the pasted text.
+    pub line: usize,
+    pub text: String,
+    pub kind: LineKind,
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Transcript {
+    pub rows: Vec<ImportRow>,
+    /// Every non-blank line, in order.
+    pub lines: Vec<TranscriptLine>,
+}
+
+/// Lines starting with these words are transcript boilerplate.
+const IGNORED_PREFIXES: [&str; 14] = [
+    "course",
+    "description",
+    "units",
+    "term gpa",
+    "term totals",
+    "cumulative",
+    "cum gpa",
+    "totals",
+    "transcript",
+    "unofficial",
+    "program",
+    "academic",
+    "student",
+    "page",
+];
+
+fn is_subject(token: &str) -> bool {
+    (2..=5).contains(&token.len()) && token.chars().all(|c| c.is_ascii_uppercase())
+}
+
+fn is_course_number(token: &str) -> bool {
+    let digits = token.chars().take_while(char::is_ascii_digit).count();
+    digits == 3 && token[digits..].chars().all(|c| c.is_ascii_uppercase())
+}
+
+/// A term heading, possibly followed by a career label such as "(Graduate)".
+fn parse_term(line: &str) -> Option<Term> {
+    let mut tokens = line.split_whitespace();
+    let heading = format!("{} {}", tokens.next()?, tokens.next()?);
+    let rest: Vec<&str> = tokens.collect();
+    if rest.len() > 2 {
+        return None;
+    }
+    heading.parse().ok()
+}
+
+fn is_decimal(token: &str) -> bool {
+    token.contains('.') && token.parse::<f32>().is_ok()
+}
+
+/// A course line, or why a line that starts like one could not be read.
+fn parse_course(line: &str) -> Option<Result<(String, String, String, String), String>> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    if tokens.len() < 2 || !is_subject(tokens[0]) || !is_course_number(tokens[1]) {
+        return None;
+    }
+    let code = format!("{} {}", tokens[0], tokens[1]);
+    let rest = &tokens[2..];
+
+    // Columns are read from the right, since titles can contain numbers ("Web 2.0")
+    let (units_at, grade) = match rest {
+        [.., units, grade, points]
+            if is_decimal(units) && !is_decimal(grade) && is_decimal(points) =>
+        {
+            (rest.len() - 3, Some(grade))
+        }
+        [.., units, grade] if is_decimal(units) && !is_decimal(grade) => {
+            (rest.len() - 2, Some(grade))
+        }
+        [.., units] if is_decimal(units) => (rest.len() - 1, None),
+        _ => return Some(Err(format!("{}: no units found", code))),
+    };
+    let Some(grade) = grade else {
+        return Some(Err(format!("{}: no grade yet", code)));
+    };
+    let title = rest[..units_at].join(" ");
+    Some(Ok((
+        code,
+        title,
+        rest[units_at].to_string(),
+        grade.to_string(),
+    )))
+}
+
+/// Transcript units are written with decimals, e.g. "3.00".
+fn parse_units(token: &str) -> Result<i32, FieldError> {
+    match token.split_once('.') {
+        Some((whole, fraction)) if fraction.chars().all(|c| c == '0') => parse_unit(whole),
+        _ => parse_unit(token),
+    }
+}
+
+/// Parses a pasted transcript and matches its courses against the catalog.
+pub fn parse_transcript(text: &str, catalog: &[Course]) -> Transcript {
+    let mut transcript = Transcript::default();
+    let mut term = None;
+
+    for (index, text) in text.lines().enumerate() {
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let lower = trimmed.to_ascii_lowercase();
+        let kind = if let Some(heading) = parse_term(trimmed) {
+            term = Some(heading);
+            LineKind::Term(heading)
+        } else if let Some(course) = parse_course(trimmed) {
+            match course {
+                Ok((code, name, units, grade)) => {
+                    let letter = grade.to_ascii_uppercase();
+                    let mut errors = vec![];
+                    if !LETTER_GRADES.contains(&letter.as_str()) {
+                        errors.push(format!("grade \"{}\" is not counted in the GPA", grade));
+                    }
+                    let unit = parse_units(&units).unwrap_or_else(|err| {
+                        errors.push(format!("{} (got \"{}\")", err, units));
+                        0
+                    });
+                    if term.is_none() {
+                        errors.push("no term heading above this course".to_string());
+                    }
+                    let target = match find_in_catalog(catalog, &code, &name) {
+                        Some(index) => ImportTarget::Catalog(catalog[index].id),
+                        None => ImportTarget::NewCourse,
+                    };
+                    transcript.rows.push(ImportRow {
+                        line: index + 1,
+                        grade: letter_to_point(&letter),
+                        name,
+                        code,
+                        unit,
+                        letter,
+                        term,
+                        target,
+                        errors,
+                    });
+                    LineKind::Course(transcript.rows.len() - 1)
+                }
+                Err(reason) => LineKind::Unrecognized(reason),
+            }
+        } else if IGNORED_PREFIXES.iter().any(|p| lower.starts_with(p)) {
+            LineKind::Ignored
+        } else {
+            LineKind::Unrecognized("not a term heading or course line".to_string())
+        };
+        transcript.lines.push(TranscriptLine {
+            line: index + 1,
+            text: trimmed.to_string(),
+            kind,
+        });
+    }
+    transcript
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+Unofficial Transcript
+Fall 2023 (Graduate)
+Course      Description                 Units   Grade   Grade Points
+CMPT 726    Machine Learning            3.00    A-      11.01
+CMPT 732    Big Data Lab I              6.00    B+
+
+Spring 2024
+CMPT 733    Big Data Lab II             6.00
+CMPT 756    Web 2.0 Systems             3.00    A+      12.99
+CMPT 880    Special Topics              45.00   A
+CMPT 881    Directed Reading            3.00    XX
+Term GPA 3.67
+";
+
+    fn parsed() -> Transcript {
+        let catalog = vec![Course {
+            id: 7,
+            code: "CMPT 726".to_string(),
+            name: "Machine Learning".to_string(),
+            unit: 3,
+            ..Course::default()
+        }];
+        parse_transcript(SAMPLE, &catalog)
+    }
+
+    #[test]
+    fn term_headings_apply_to_the_courses_below() {
+        let transcript = parsed();
+        let terms: Vec<String> = transcript
+            .rows
+            .iter()
+            .map(|row| row.term.map(|t| t.to_string()).unwrap_or_default())
+            .collect();
+        assert_eq!(
+            terms,
+            [
+                "2023 Fall",
+                "2023 Fall",
+                "2024 Spring",
+                "2024 Spring",
+                "2024 Spring"
+            ]
+        );
+        assert_eq!(
+            transcript.lines[1].kind,
+            LineKind::Term("Fall 2023".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn course_lines_are_read_by_column() {
+        let transcript = parsed();
+        let row = &transcript.rows[0];
+        assert_eq!(
+            (
+                row.code.as_str(),
+                row.name.as_str(),
+                row.unit,
+                row.letter.as_str()
+            ),
+            ("CMPT 726", "Machine Learning", 3, "A-")
+        );
+        assert_eq!(row.target, ImportTarget::Catalog(7));
+        assert!(row.is_valid());
+        assert_eq!(transcript.rows[1].target, ImportTarget::NewCourse);
+
+        // A number in the title is not taken for the units
+        let web = &transcript.rows[2];
+        assert_eq!((web.name.as_str(), web.unit), ("Web 2.0 Systems", 3));
+    }
+
+    #[test]
+    fn courses_without_a_grade_are_reported_on_their_line() {
+        let transcript = parsed();
+        let line = transcript.lines.iter().find(|l| l.line == 8).unwrap();
+        assert_eq!(
+            line.kind,
+            LineKind::Unrecognized("CMPT 733: no grade yet".to_string())
+        );
+    }
+
+    #[test]
+    fn bad_units_and_grades_are_row_errors_with_their_line() {
+        let transcript = parsed();
+        let topics = &transcript.rows[3];
+        assert_eq!(topics.line, 10);
+        assert_eq!(
+            topics.errors,
+            ["Units must be a whole number from 1 to 30 (got \"45.00\")"]
+        );
+        let reading = &transcript.rows[4];
+        assert_eq!(reading.line, 11);
+        assert_eq!(reading.errors, ["grade \"XX\" is not counted in the GPA"]);
+    }
+
+    #[test]
+    fn lines_without_units_are_unrecognized() {
+        assert_eq!(
+            parse_course("CMPT 726 Machine Learning A"),
+            Some(Err("CMPT 726: no units found".to_string()))
+        );
+        assert_eq!(parse_course("Term GPA 3.67"), None);
+    }
+}